@@ -34,11 +34,20 @@ impl App {
 	}
 
 	pub async fn run(&mut self) -> Result<()> {
-		for arg in env::args() {
+		let mut args = env::args().skip(1);
+
+		while let Some(arg) = args.next() {
 			if arg == "--show-save-path" {
 				self.save.show_save_path();
 				return Ok(());
 			}
+			if arg == "--seed" {
+				let seed = args
+					.next()
+					.and_then(|v| v.parse().ok())
+					.ok_or(anyhow::anyhow!("--seed expects a number"))?;
+				self.state.seed = Some(seed);
+			}
 		}
 
 		self.term.init()?;
@@ -54,7 +63,9 @@ impl App {
 			}
 
 			if event == Event::Tick {
+				self.state.update_stopwatch();
 				self.state.update_line_clear();
+				self.state.update_entry_delay();
 				self.term.draw(|f| {
 					ui(f, &mut self.state);
 				})?;
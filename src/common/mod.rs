@@ -2,6 +2,7 @@ mod point;
 mod position;
 mod tetromino_kind;
 
+pub use point::Point;
 pub use position::{pos, Position};
 pub use tetromino_kind::TetrominoKind;
 
@@ -6,10 +6,19 @@ use std::{
 use serde::{Deserialize, Serialize};
 
 use super::point::Point;
-use crate::consts::{MAIN_BOARD_BUFFER_ROWS, MAIN_BOARD_COLS, MAIN_BOARD_ROWS};
+use crate::{
+	consts::MAIN_BOARD_BUFFER_ROWS,
+	global::{board_cols, board_rows},
+};
+
+fn max_y() -> i8 {
+	board_rows() as i8 - 1
+}
+
+fn max_x() -> i8 {
+	board_cols() as i8 - 1
+}
 
-const MAX_Y: i8 = MAIN_BOARD_ROWS as i8 - 1;
-const MAX_X: i8 = MAIN_BOARD_COLS as i8 - 1;
 const MIN_Y: i8 = MAIN_BOARD_BUFFER_ROWS as i8;
 
 #[derive(Clone, PartialEq, Eq, Deserialize, Serialize)]
@@ -35,7 +44,7 @@ impl Position {
 	}
 
 	pub fn is_touch_bottom(&self) -> bool {
-		self.0.iter().any(|p| p.y >= MAX_Y)
+		self.0.iter().any(|p| p.y >= max_y())
 	}
 
 	pub fn is_touch_left(&self) -> bool {
@@ -43,19 +52,20 @@ impl Position {
 	}
 
 	pub fn is_touch_right(&self) -> bool {
-		self.0.iter().any(|p| p.x >= MAX_X)
+		self.0.iter().any(|p| p.x >= max_x())
 	}
 
 	pub fn is_outside_the_board(&self) -> bool {
+		let (max_x, max_y) = (max_x(), max_y());
+
 		self.0
 			.iter()
-			.any(|p| p.x < 0 || p.x > MAX_X || p.y < 0 || p.y > MAX_Y)
+			.any(|p| p.x < 0 || p.x > max_x || p.y < 0 || p.y > max_y)
 	}
 
-	pub fn is_outside_the_visible(&self) -> bool {
-		self.0
-			.iter()
-			.any(|p| p.x < 0 || p.x > MAX_X || p.y < MIN_Y || p.y > MAX_Y)
+	/// Whether every point is in the buffer rows
+	pub fn is_above_the_visible(&self) -> bool {
+		self.0.iter().all(|p| p.y < MIN_Y)
 	}
 
 	pub fn to_usize_points(&self) -> Vec<Point<usize>> {
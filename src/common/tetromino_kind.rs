@@ -29,9 +29,22 @@ pub enum TetrominoKind {
 	///   [][]
 	/// ```
 	Z,
+	/// Cells of garbage rows, never dealt as a piece
+	Garbage,
 }
 
 impl TetrominoKind {
+	/// The kinds that can be dealt
+	pub const ALL: [TetrominoKind; 7] = [
+		TetrominoKind::I,
+		TetrominoKind::J,
+		TetrominoKind::L,
+		TetrominoKind::O,
+		TetrominoKind::S,
+		TetrominoKind::T,
+		TetrominoKind::Z,
+	];
+
 	pub fn init_position(&self, idx: usize) -> Position {
 		let position = match self {
 			TetrominoKind::I => &position_map::I[idx],
@@ -41,6 +54,7 @@ impl TetrominoKind {
 			TetrominoKind::S => &position_map::S[idx],
 			TetrominoKind::T => &position_map::T[idx],
 			TetrominoKind::Z => &position_map::Z[idx],
+			TetrominoKind::Garbage => unreachable!("garbage is never spawned"),
 		};
 
 		position.clone()
@@ -55,6 +69,7 @@ impl TetrominoKind {
 			TetrominoKind::S => color::green(),
 			TetrominoKind::T => color::purple(),
 			TetrominoKind::Z => color::red(),
+			TetrominoKind::Garbage => color::grey(),
 		}
 	}
 
@@ -67,6 +82,7 @@ impl TetrominoKind {
 			TetrominoKind::S => color::dark_green(),
 			TetrominoKind::T => color::dark_purple(),
 			TetrominoKind::Z => color::dark_red(),
+			TetrominoKind::Garbage => color::dark_grey(),
 		}
 	}
 }
@@ -157,6 +173,10 @@ mod color {
 		Color::Rgb(128, 0, 128)
 	}
 
+	pub fn grey() -> Color {
+		Color::Rgb(105, 105, 105)
+	}
+
 	pub fn dark_red() -> Color {
 		Color::Rgb(139, 0, 0)
 	}
@@ -184,4 +204,8 @@ mod color {
 	pub fn dark_purple() -> Color {
 		Color::Rgb(64, 0, 64)
 	}
+
+	pub fn dark_grey() -> Color {
+		Color::Rgb(64, 64, 64)
+	}
 }
@@ -7,11 +7,19 @@ pub const FRAME_RATE_SECS: f32 = 0.033;
 pub const MIN_CELL_WIDTH: u16 = 5;
 pub const MIN_CELL_HEIGHT: u16 = 3;
 
+// The board size can be changed in the setting, these are the defaults
 pub const MAIN_BOARD_COLS: usize = 10;
 pub const MAIN_BOARD_BUFFER_ROWS: usize = 5;
 pub const MAIN_BOARD_VISIBLE_ROWS: usize = 16;
-pub const MAIN_BOARD_ROWS: usize =
-	MAIN_BOARD_VISIBLE_ROWS + MAIN_BOARD_BUFFER_ROWS;
 
-pub const NEXT_BOARD_COLS: usize = 8;
+pub const MAIN_BOARD_MIN_COLS: usize = 4;
+pub const MAIN_BOARD_MAX_COLS: usize = 20;
+pub const MAIN_BOARD_MIN_VISIBLE_ROWS: usize = 10;
+pub const MAIN_BOARD_MAX_VISIBLE_ROWS: usize = 30;
+
+pub const NEXT_BOARD_COLS: usize = 4;
 pub const NEXT_BOARD_ROWS: usize = 2;
+pub const NEXT_QUEUE_MAX_LEN: usize = 6;
+
+pub const HOLD_BOARD_COLS: usize = 4;
+pub const HOLD_BOARD_ROWS: usize = 2;
@@ -1,14 +1,22 @@
 mod audio;
 pub mod setting;
 
-use std::sync::atomic::{AtomicBool, Ordering::Relaxed};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering::Relaxed};
 
 pub use audio::{global_audio, init_global_audio, Sound};
 pub use setting::global_setting;
 
+use crate::consts::{
+	MAIN_BOARD_BUFFER_ROWS, MAIN_BOARD_COLS, MAIN_BOARD_VISIBLE_ROWS,
+};
+
 static PAUSED: AtomicBool = AtomicBool::new(false);
 static LOCKED: AtomicBool = AtomicBool::new(false);
 static PLAYED: AtomicBool = AtomicBool::new(false);
+static KEY_RELEASE: AtomicBool = AtomicBool::new(false);
+static BOARD_COLS: AtomicUsize = AtomicUsize::new(MAIN_BOARD_COLS);
+static BOARD_VISIBLE_ROWS: AtomicUsize =
+	AtomicUsize::new(MAIN_BOARD_VISIBLE_ROWS);
 
 pub fn is_paused() -> bool {
 	PAUSED.load(Relaxed)
@@ -33,3 +41,33 @@ pub fn is_played() -> bool {
 pub fn set_played(v: bool) {
 	PLAYED.store(v, Relaxed);
 }
+
+/// Whether the terminal reports key release events
+pub fn is_key_release_supported() -> bool {
+	KEY_RELEASE.load(Relaxed)
+}
+
+pub fn set_key_release_supported(v: bool) {
+	KEY_RELEASE.store(v, Relaxed);
+}
+
+/// Columns of the board in play
+pub fn board_cols() -> usize {
+	BOARD_COLS.load(Relaxed)
+}
+
+/// Visible rows of the board in play
+pub fn board_visible_rows() -> usize {
+	BOARD_VISIBLE_ROWS.load(Relaxed)
+}
+
+/// All rows of the board in play, including the buffer rows above the
+/// visible ones
+pub fn board_rows() -> usize {
+	board_visible_rows() + MAIN_BOARD_BUFFER_ROWS
+}
+
+pub fn set_board_size(cols: usize, visible_rows: usize) {
+	BOARD_COLS.store(cols, Relaxed);
+	BOARD_VISIBLE_ROWS.store(visible_rows, Relaxed);
+}
@@ -1,11 +1,16 @@
 use std::sync::{
-	atomic::{AtomicBool, Ordering::Relaxed},
+	atomic::{AtomicBool, AtomicU16, AtomicU8, Ordering::Relaxed},
 	OnceLock,
 };
 
 use serde::{Deserialize, Serialize};
 
 use super::global_audio;
+use crate::consts::{
+	MAIN_BOARD_COLS, MAIN_BOARD_MAX_COLS, MAIN_BOARD_MAX_VISIBLE_ROWS,
+	MAIN_BOARD_MIN_COLS, MAIN_BOARD_MIN_VISIBLE_ROWS, MAIN_BOARD_VISIBLE_ROWS,
+	NEXT_QUEUE_MAX_LEN,
+};
 
 static SETTING: OnceLock<Setting> = OnceLock::new();
 
@@ -17,6 +22,34 @@ pub struct Setting {
 	particle: AtomicBool,
 	music: AtomicBool,
 	sound: AtomicBool,
+	next_count: AtomicU8,
+	das: AtomicU16,
+	arr: AtomicU16,
+	soft_drop: AtomicU8,
+	gravity: AtomicU8,
+	randomizer: AtomicU8,
+	lock_delay: AtomicU16,
+	lock_reset: AtomicU8,
+	lock_reset_limit: AtomicU8,
+	board_cols: AtomicU8,
+	board_visible_rows: AtomicU8,
+	sprint_lines: AtomicU16,
+	ultra_minutes: AtomicU8,
+	dig_rows: AtomicU8,
+	dig_messiness: AtomicU8,
+	garbage_interval: AtomicU8,
+	start_level: AtomicU8,
+	stack: AtomicU8,
+	mirror: AtomicBool,
+	no_ghost: AtomicBool,
+	no_next: AtomicBool,
+	monochrome: AtomicBool,
+	big: AtomicBool,
+	scoring: AtomicU8,
+	entry_delay: AtomicU16,
+	line_clear_delay: AtomicU16,
+	spawn_up: AtomicBool,
+	practice_gravity: AtomicBool,
 }
 
 impl Setting {
@@ -25,6 +58,34 @@ impl Setting {
 			particle: AtomicBool::new(false),
 			music: AtomicBool::new(false),
 			sound: AtomicBool::new(false),
+			next_count: AtomicU8::new(DEFAULT_NEXT_COUNT),
+			das: AtomicU16::new(DEFAULT_DAS),
+			arr: AtomicU16::new(DEFAULT_ARR),
+			soft_drop: AtomicU8::new(DEFAULT_SOFT_DROP),
+			gravity: AtomicU8::new(GravityCurve::default() as u8),
+			randomizer: AtomicU8::new(RandomizerKind::default() as u8),
+			lock_delay: AtomicU16::new(DEFAULT_LOCK_DELAY),
+			lock_reset: AtomicU8::new(LockReset::default() as u8),
+			lock_reset_limit: AtomicU8::new(DEFAULT_LOCK_RESET_LIMIT),
+			board_cols: AtomicU8::new(MAIN_BOARD_COLS as u8),
+			board_visible_rows: AtomicU8::new(MAIN_BOARD_VISIBLE_ROWS as u8),
+			sprint_lines: AtomicU16::new(DEFAULT_SPRINT_LINES),
+			ultra_minutes: AtomicU8::new(DEFAULT_ULTRA_MINUTES),
+			dig_rows: AtomicU8::new(DEFAULT_DIG_ROWS),
+			dig_messiness: AtomicU8::new(DEFAULT_DIG_MESSINESS),
+			garbage_interval: AtomicU8::new(DEFAULT_GARBAGE_INTERVAL),
+			start_level: AtomicU8::new(1),
+			stack: AtomicU8::new(StackVisibility::default() as u8),
+			mirror: AtomicBool::new(false),
+			no_ghost: AtomicBool::new(false),
+			no_next: AtomicBool::new(false),
+			monochrome: AtomicBool::new(false),
+			big: AtomicBool::new(false),
+			scoring: AtomicU8::new(ScoringSystem::default() as u8),
+			entry_delay: AtomicU16::new(DEFAULT_ENTRY_DELAY),
+			line_clear_delay: AtomicU16::new(DEFAULT_LINE_CLEAR_DELAY),
+			spawn_up: AtomicBool::new(false),
+			practice_gravity: AtomicBool::new(true),
 		}
 	}
 
@@ -40,11 +101,181 @@ impl Setting {
 		self.sound.load(Relaxed)
 	}
 
+	pub fn next_count(&self) -> usize {
+		self.next_count.load(Relaxed) as usize
+	}
+
+	/// Delayed auto shift in milliseconds
+	pub fn das(&self) -> u16 {
+		self.das.load(Relaxed)
+	}
+
+	/// Auto repeat rate in milliseconds, 0 means shifting to the wall
+	/// instantly
+	pub fn arr(&self) -> u16 {
+		self.arr.load(Relaxed)
+	}
+
+	/// Soft drop speed as a multiple of the gravity, or [`SONIC_DROP`]
+	pub fn soft_drop(&self) -> u8 {
+		self.soft_drop.load(Relaxed)
+	}
+
+	pub fn gravity(&self) -> GravityCurve {
+		GravityCurve::from(self.gravity.load(Relaxed))
+	}
+
+	pub fn randomizer(&self) -> RandomizerKind {
+		RandomizerKind::from(self.randomizer.load(Relaxed))
+	}
+
+	/// Lock delay in milliseconds
+	pub fn lock_delay(&self) -> u16 {
+		self.lock_delay.load(Relaxed)
+	}
+
+	pub fn lock_reset(&self) -> LockReset {
+		LockReset::from(self.lock_reset.load(Relaxed))
+	}
+
+	/// How many times moving or rotating can reset the lock delay under
+	/// [`LockReset::Move`]
+	pub fn lock_reset_limit(&self) -> u8 {
+		self.lock_reset_limit.load(Relaxed)
+	}
+
+	/// Board width of new games
+	pub fn board_cols(&self) -> usize {
+		self.board_cols.load(Relaxed) as usize
+	}
+
+	/// Board height of new games, without the buffer rows
+	pub fn board_visible_rows(&self) -> usize {
+		self.board_visible_rows.load(Relaxed) as usize
+	}
+
+	/// Line goal of the sprint mode
+	pub fn sprint_lines(&self) -> u32 {
+		self.sprint_lines.load(Relaxed) as u32
+	}
+
+	/// Time limit of the ultra mode in minutes
+	pub fn ultra_minutes(&self) -> u32 {
+		self.ultra_minutes.load(Relaxed) as u32
+	}
+
+	/// Garbage rows the dig mode starts with
+	pub fn dig_rows(&self) -> usize {
+		self.dig_rows.load(Relaxed) as usize
+	}
+
+	/// Chance in percent that a garbage row has its hole in another column
+	/// than the row below
+	pub fn dig_messiness(&self) -> u8 {
+		self.dig_messiness.load(Relaxed)
+	}
+
+	/// Seconds between rising garbage rows in the dig mode, 0 means off
+	pub fn garbage_interval(&self) -> u8 {
+		self.garbage_interval.load(Relaxed)
+	}
+
+	/// Level the marathon modes start at
+	pub fn start_level(&self) -> u32 {
+		self.start_level.load(Relaxed) as u32
+	}
+
+	pub fn stack(&self) -> StackVisibility {
+		StackVisibility::from(self.stack.load(Relaxed))
+	}
+
+	/// Left and right, and the rotation directions, are swapped
+	pub fn mirror(&self) -> bool {
+		self.mirror.load(Relaxed)
+	}
+
+	pub fn no_ghost(&self) -> bool {
+		self.no_ghost.load(Relaxed)
+	}
+
+	pub fn no_next(&self) -> bool {
+		self.no_next.load(Relaxed)
+	}
+
+	pub fn monochrome(&self) -> bool {
+		self.monochrome.load(Relaxed)
+	}
+
+	/// Every cell of a piece takes 2x2 cells of the board
+	pub fn big(&self) -> bool {
+		self.big.load(Relaxed)
+	}
+
+	pub fn scoring(&self) -> ScoringSystem {
+		ScoringSystem::from(self.scoring.load(Relaxed))
+	}
+
+	/// Delay before the next piece enters, known as ARE, in milliseconds
+	pub fn entry_delay(&self) -> u16 {
+		self.entry_delay.load(Relaxed)
+	}
+
+	/// How long cleared lines stay before they are removed, in milliseconds
+	pub fn line_clear_delay(&self) -> u16 {
+		self.line_clear_delay.load(Relaxed)
+	}
+
+	/// A piece whose spawn position is blocked spawns one row higher when
+	/// that row is free
+	pub fn spawn_up(&self) -> bool {
+		self.spawn_up.load(Relaxed)
+	}
+
+	/// Whether pieces fall by themselves in the practice mode
+	pub fn practice_gravity(&self) -> bool {
+		self.practice_gravity.load(Relaxed)
+	}
+
 	pub fn switch_particle(&self) {
 		let previous = self.particle.load(Relaxed);
 		self.particle.store(!previous, Relaxed);
 	}
 
+	pub fn switch_mirror(&self) {
+		let previous = self.mirror.load(Relaxed);
+		self.mirror.store(!previous, Relaxed);
+	}
+
+	pub fn switch_no_ghost(&self) {
+		let previous = self.no_ghost.load(Relaxed);
+		self.no_ghost.store(!previous, Relaxed);
+	}
+
+	pub fn switch_no_next(&self) {
+		let previous = self.no_next.load(Relaxed);
+		self.no_next.store(!previous, Relaxed);
+	}
+
+	pub fn switch_monochrome(&self) {
+		let previous = self.monochrome.load(Relaxed);
+		self.monochrome.store(!previous, Relaxed);
+	}
+
+	pub fn switch_big(&self) {
+		let previous = self.big.load(Relaxed);
+		self.big.store(!previous, Relaxed);
+	}
+
+	pub fn switch_spawn_up(&self) {
+		let previous = self.spawn_up.load(Relaxed);
+		self.spawn_up.store(!previous, Relaxed);
+	}
+
+	pub fn switch_practice_gravity(&self) {
+		let previous = self.practice_gravity.load(Relaxed);
+		self.practice_gravity.store(!previous, Relaxed);
+	}
+
 	pub fn switch_music(&self) {
 		let previous = self.music.load(Relaxed);
 		self.music.store(!previous, Relaxed);
@@ -63,11 +294,216 @@ impl Setting {
 		}
 	}
 
+	pub fn change_next_count(&self, forward: bool) {
+		let next =
+			cycle(self.next_count() - 1, NEXT_QUEUE_MAX_LEN - 1, forward);
+		self.next_count.store(next as u8 + 1, Relaxed);
+	}
+
+	pub fn change_das(&self, forward: bool) {
+		self.das
+			.store(cycle_option(&DAS_OPTIONS, self.das(), forward), Relaxed);
+	}
+
+	pub fn change_arr(&self, forward: bool) {
+		self.arr
+			.store(cycle_option(&ARR_OPTIONS, self.arr(), forward), Relaxed);
+	}
+
+	pub fn change_soft_drop(&self, forward: bool) {
+		self.soft_drop.store(
+			cycle_option(&SOFT_DROP_OPTIONS, self.soft_drop(), forward),
+			Relaxed,
+		);
+	}
+
+	pub fn change_gravity(&self, forward: bool) {
+		let next =
+			cycle(self.gravity() as usize, GravityCurve::Tgm as usize, forward);
+		self.gravity.store(next as u8, Relaxed);
+	}
+
+	pub fn change_scoring(&self, forward: bool) {
+		let next = cycle(
+			self.scoring() as usize,
+			ScoringSystem::Tgm as usize,
+			forward,
+		);
+		self.scoring.store(next as u8, Relaxed);
+	}
+
+	pub fn change_entry_delay(&self, forward: bool) {
+		self.entry_delay.store(
+			cycle_option(&ENTRY_DELAY_OPTIONS, self.entry_delay(), forward),
+			Relaxed,
+		);
+	}
+
+	pub fn change_line_clear_delay(&self, forward: bool) {
+		self.line_clear_delay.store(
+			cycle_option(
+				&LINE_CLEAR_DELAY_OPTIONS,
+				self.line_clear_delay(),
+				forward,
+			),
+			Relaxed,
+		);
+	}
+
+	pub fn change_randomizer(&self, forward: bool) {
+		let next = cycle(
+			self.randomizer() as usize,
+			RandomizerKind::TgmHistory as usize,
+			forward,
+		);
+		self.randomizer.store(next as u8, Relaxed);
+	}
+
+	pub fn change_lock_delay(&self, forward: bool) {
+		self.lock_delay.store(
+			cycle_option(&LOCK_DELAY_OPTIONS, self.lock_delay(), forward),
+			Relaxed,
+		);
+	}
+
+	pub fn change_lock_reset(&self, forward: bool) {
+		let next = cycle(
+			self.lock_reset() as usize,
+			LockReset::Infinite as usize,
+			forward,
+		);
+		self.lock_reset.store(next as u8, Relaxed);
+	}
+
+	pub fn change_lock_reset_limit(&self, forward: bool) {
+		self.lock_reset_limit.store(
+			cycle_option(
+				&LOCK_RESET_LIMIT_OPTIONS,
+				self.lock_reset_limit(),
+				forward,
+			),
+			Relaxed,
+		);
+	}
+
+	pub fn change_board_cols(&self, forward: bool) {
+		let next = cycle(
+			self.board_cols() - MAIN_BOARD_MIN_COLS,
+			MAIN_BOARD_MAX_COLS - MAIN_BOARD_MIN_COLS,
+			forward,
+		);
+		self.board_cols
+			.store((next + MAIN_BOARD_MIN_COLS) as u8, Relaxed);
+	}
+
+	pub fn change_board_visible_rows(&self, forward: bool) {
+		let next = cycle(
+			self.board_visible_rows() - MAIN_BOARD_MIN_VISIBLE_ROWS,
+			MAIN_BOARD_MAX_VISIBLE_ROWS - MAIN_BOARD_MIN_VISIBLE_ROWS,
+			forward,
+		);
+		self.board_visible_rows
+			.store((next + MAIN_BOARD_MIN_VISIBLE_ROWS) as u8, Relaxed);
+	}
+
+	pub fn change_sprint_lines(&self, forward: bool) {
+		self.sprint_lines.store(
+			cycle_option(
+				&SPRINT_LINES_OPTIONS,
+				self.sprint_lines() as u16,
+				forward,
+			),
+			Relaxed,
+		);
+	}
+
+	pub fn change_ultra_minutes(&self, forward: bool) {
+		self.ultra_minutes.store(
+			cycle_option(
+				&ULTRA_MINUTES_OPTIONS,
+				self.ultra_minutes() as u8,
+				forward,
+			),
+			Relaxed,
+		);
+	}
+
+	pub fn change_dig_rows(&self, forward: bool) {
+		self.dig_rows.store(
+			cycle_option(&DIG_ROWS_OPTIONS, self.dig_rows() as u8, forward),
+			Relaxed,
+		);
+	}
+
+	pub fn change_dig_messiness(&self, forward: bool) {
+		self.dig_messiness.store(
+			cycle_option(&DIG_MESSINESS_OPTIONS, self.dig_messiness(), forward),
+			Relaxed,
+		);
+	}
+
+	pub fn change_garbage_interval(&self, forward: bool) {
+		self.garbage_interval.store(
+			cycle_option(
+				&GARBAGE_INTERVAL_OPTIONS,
+				self.garbage_interval(),
+				forward,
+			),
+			Relaxed,
+		);
+	}
+
+	pub fn change_stack(&self, forward: bool) {
+		let next = cycle(
+			self.stack() as usize,
+			StackVisibility::Invisible as usize,
+			forward,
+		);
+		self.stack.store(next as u8, Relaxed);
+	}
+
+	pub fn change_start_level(&self, forward: bool) {
+		let next = cycle(
+			self.start_level() as usize - 1,
+			MAX_START_LEVEL as usize - 1,
+			forward,
+		);
+		self.start_level.store(next as u8 + 1, Relaxed);
+	}
+
 	pub fn to_save_content(&self) -> SettingSave {
 		SettingSave {
 			particle: self.particle(),
 			music: self.music(),
 			sound: self.sound(),
+			next_count: self.next_count.load(Relaxed),
+			das: self.das(),
+			arr: self.arr(),
+			soft_drop: self.soft_drop(),
+			gravity: self.gravity(),
+			randomizer: self.randomizer(),
+			lock_delay: self.lock_delay(),
+			lock_reset: self.lock_reset(),
+			lock_reset_limit: self.lock_reset_limit(),
+			board_cols: self.board_cols() as u8,
+			board_visible_rows: self.board_visible_rows() as u8,
+			sprint_lines: self.sprint_lines() as u16,
+			ultra_minutes: self.ultra_minutes() as u8,
+			dig_rows: self.dig_rows() as u8,
+			dig_messiness: self.dig_messiness(),
+			garbage_interval: self.garbage_interval(),
+			start_level: self.start_level() as u8,
+			stack: self.stack(),
+			mirror: self.mirror(),
+			no_ghost: self.no_ghost(),
+			no_next: self.no_next(),
+			monochrome: self.monochrome(),
+			big: self.big(),
+			scoring: self.scoring(),
+			entry_delay: self.entry_delay(),
+			line_clear_delay: self.line_clear_delay(),
+			spawn_up: self.spawn_up(),
+			practice_gravity: self.practice_gravity(),
 		}
 	}
 
@@ -75,6 +511,61 @@ impl Setting {
 		self.particle.store(content.particle, Relaxed);
 		self.music.store(content.music, Relaxed);
 		self.sound.store(content.sound, Relaxed);
+		self.next_count.store(
+			content.next_count.clamp(1, NEXT_QUEUE_MAX_LEN as u8),
+			Relaxed,
+		);
+		self.das
+			.store(snap_option(&DAS_OPTIONS, content.das), Relaxed);
+		self.arr
+			.store(snap_option(&ARR_OPTIONS, content.arr), Relaxed);
+		self.soft_drop
+			.store(snap_option(&SOFT_DROP_OPTIONS, content.soft_drop), Relaxed);
+		self.gravity.store(content.gravity as u8, Relaxed);
+		self.randomizer.store(content.randomizer as u8, Relaxed);
+		self.lock_delay.store(content.lock_delay.max(1), Relaxed);
+		self.lock_reset.store(content.lock_reset as u8, Relaxed);
+		self.lock_reset_limit.store(
+			snap_option(&LOCK_RESET_LIMIT_OPTIONS, content.lock_reset_limit),
+			Relaxed,
+		);
+		self.board_cols.store(
+			content
+				.board_cols
+				.clamp(MAIN_BOARD_MIN_COLS as u8, MAIN_BOARD_MAX_COLS as u8),
+			Relaxed,
+		);
+		self.board_visible_rows.store(
+			content.board_visible_rows.clamp(
+				MAIN_BOARD_MIN_VISIBLE_ROWS as u8,
+				MAIN_BOARD_MAX_VISIBLE_ROWS as u8,
+			),
+			Relaxed,
+		);
+		self.sprint_lines
+			.store(content.sprint_lines.max(1), Relaxed);
+		self.ultra_minutes
+			.store(content.ultra_minutes.max(1), Relaxed);
+		self.dig_rows.store(content.dig_rows.max(1), Relaxed);
+		self.dig_messiness
+			.store(content.dig_messiness.min(100), Relaxed);
+		self.garbage_interval
+			.store(content.garbage_interval, Relaxed);
+		self.start_level
+			.store(content.start_level.clamp(1, MAX_START_LEVEL), Relaxed);
+		self.stack.store(content.stack as u8, Relaxed);
+		self.mirror.store(content.mirror, Relaxed);
+		self.no_ghost.store(content.no_ghost, Relaxed);
+		self.no_next.store(content.no_next, Relaxed);
+		self.monochrome.store(content.monochrome, Relaxed);
+		self.big.store(content.big, Relaxed);
+		self.scoring.store(content.scoring as u8, Relaxed);
+		self.entry_delay.store(content.entry_delay, Relaxed);
+		self.line_clear_delay
+			.store(content.line_clear_delay, Relaxed);
+		self.spawn_up.store(content.spawn_up, Relaxed);
+		self.practice_gravity
+			.store(content.practice_gravity, Relaxed);
 		if content.sound {
 			global_audio(|audio| audio.stop_sound());
 		}
@@ -86,4 +577,320 @@ pub struct SettingSave {
 	particle: bool,
 	music: bool,
 	sound: bool,
+	#[serde(default = "default_next_count")]
+	next_count: u8,
+	#[serde(default = "default_das")]
+	das: u16,
+	#[serde(default = "default_arr")]
+	arr: u16,
+	#[serde(default = "default_soft_drop")]
+	soft_drop: u8,
+	#[serde(default)]
+	gravity: GravityCurve,
+	#[serde(default)]
+	randomizer: RandomizerKind,
+	#[serde(default = "default_lock_delay")]
+	lock_delay: u16,
+	#[serde(default)]
+	lock_reset: LockReset,
+	#[serde(default = "default_lock_reset_limit")]
+	lock_reset_limit: u8,
+	#[serde(default = "default_board_cols")]
+	board_cols: u8,
+	#[serde(default = "default_board_visible_rows")]
+	board_visible_rows: u8,
+	#[serde(default = "default_sprint_lines")]
+	sprint_lines: u16,
+	#[serde(default = "default_ultra_minutes")]
+	ultra_minutes: u8,
+	#[serde(default = "default_dig_rows")]
+	dig_rows: u8,
+	#[serde(default = "default_dig_messiness")]
+	dig_messiness: u8,
+	#[serde(default)]
+	garbage_interval: u8,
+	#[serde(default = "default_start_level")]
+	start_level: u8,
+	#[serde(default)]
+	stack: StackVisibility,
+	#[serde(default)]
+	mirror: bool,
+	#[serde(default)]
+	no_ghost: bool,
+	#[serde(default)]
+	no_next: bool,
+	#[serde(default)]
+	monochrome: bool,
+	#[serde(default)]
+	big: bool,
+	#[serde(default)]
+	scoring: ScoringSystem,
+	#[serde(default)]
+	entry_delay: u16,
+	#[serde(default = "default_line_clear_delay")]
+	line_clear_delay: u16,
+	#[serde(default)]
+	spawn_up: bool,
+	#[serde(default = "default_practice_gravity")]
+	practice_gravity: bool,
+}
+
+/// How the gravity speeds up with the level
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum GravityCurve {
+	#[default]
+	Guideline,
+	/// Frames per row of the NES version
+	Nes,
+	/// Internal gravity of the Arcade version, ends in 20G
+	Tgm,
+}
+
+impl From<u8> for GravityCurve {
+	fn from(value: u8) -> Self {
+		match value {
+			1 => Self::Nes,
+			2 => Self::Tgm,
+			_ => Self::Guideline,
+		}
+	}
+}
+
+/// How line clears and drops are scored
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ScoringSystem {
+	/// Level multiplied points with T-spin, back-to-back, combo and perfect
+	/// clear bonuses
+	#[default]
+	Guideline,
+	/// 40, 100, 300 or 1200 points multiplied by the level, only soft drops
+	/// score
+	Nes,
+	/// Score of the Arcade version, which decides the grade
+	Tgm,
+}
+
+impl From<u8> for ScoringSystem {
+	fn from(value: u8) -> Self {
+		match value {
+			1 => Self::Nes,
+			2 => Self::Tgm,
+			_ => Self::Guideline,
+		}
+	}
+}
+
+/// Which generator a new game deals its pieces from
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RandomizerKind {
+	#[default]
+	SevenBag,
+	FourteenBag,
+	Random,
+	NesReroll,
+	TgmHistory,
+}
+
+impl From<u8> for RandomizerKind {
+	fn from(value: u8) -> Self {
+		match value {
+			1 => Self::FourteenBag,
+			2 => Self::Random,
+			3 => Self::NesReroll,
+			4 => Self::TgmHistory,
+			_ => Self::SevenBag,
+		}
+	}
+}
+
+/// What can give the alive tetromino its lock delay back
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum LockReset {
+	/// Moving or rotating, up to the reset limit, the limit is restored
+	/// when the piece reaches a new lowest row
+	#[default]
+	Move,
+	/// Only reaching a new lowest row
+	Step,
+	/// Moving or rotating, without any limit
+	Infinite,
+}
+
+impl From<u8> for LockReset {
+	fn from(value: u8) -> Self {
+		match value {
+			1 => Self::Step,
+			2 => Self::Infinite,
+			_ => Self::Move,
+		}
+	}
+}
+
+/// How long locked cells stay visible
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum StackVisibility {
+	#[default]
+	Normal,
+	/// Cells fade out a few seconds after locking
+	Fading,
+	/// Cells disappear as soon as they lock
+	Invisible,
+}
+
+impl From<u8> for StackVisibility {
+	fn from(value: u8) -> Self {
+		match value {
+			1 => Self::Fading,
+			2 => Self::Invisible,
+			_ => Self::Normal,
+		}
+	}
+}
+
+const DEFAULT_NEXT_COUNT: u8 = 3;
+
+fn default_next_count() -> u8 {
+	DEFAULT_NEXT_COUNT
+}
+
+// Multiples of a 60 Hz frame
+const DAS_OPTIONS: [u16; 13] =
+	[50, 67, 83, 100, 117, 133, 150, 167, 183, 200, 233, 267, 300];
+const DEFAULT_DAS: u16 = 167;
+
+const ARR_OPTIONS: [u16; 7] = [0, 17, 33, 50, 67, 83, 100];
+const DEFAULT_ARR: u16 = 33;
+
+/// Moves the piece straight to the ghost position without locking it
+pub const SONIC_DROP: u8 = u8::MAX;
+
+const SOFT_DROP_OPTIONS: [u8; 6] = [2, 5, 10, 20, 40, SONIC_DROP];
+const DEFAULT_SOFT_DROP: u8 = 20;
+
+const LOCK_DELAY_OPTIONS: [u16; 10] =
+	[100, 200, 300, 400, 500, 600, 700, 800, 1000, 2000];
+const DEFAULT_LOCK_DELAY: u16 = 500;
+
+const LOCK_RESET_LIMIT_OPTIONS: [u8; 6] = [5, 10, 15, 20, 30, 50];
+const DEFAULT_LOCK_RESET_LIMIT: u8 = 15;
+
+const SPRINT_LINES_OPTIONS: [u16; 4] = [10, 20, 40, 100];
+const DEFAULT_SPRINT_LINES: u16 = 40;
+
+const ULTRA_MINUTES_OPTIONS: [u8; 2] = [2, 3];
+const DEFAULT_ULTRA_MINUTES: u8 = 3;
+
+const DIG_ROWS_OPTIONS: [u8; 4] = [4, 8, 10, 12];
+const DEFAULT_DIG_ROWS: u8 = 10;
+
+const DIG_MESSINESS_OPTIONS: [u8; 5] = [0, 25, 50, 75, 100];
+const DEFAULT_DIG_MESSINESS: u8 = 100;
+
+const GARBAGE_INTERVAL_OPTIONS: [u8; 6] = [0, 5, 10, 15, 20, 30];
+const DEFAULT_GARBAGE_INTERVAL: u8 = 0;
+
+const MAX_START_LEVEL: u8 = 15;
+
+const ENTRY_DELAY_OPTIONS: [u16; 9] = [0, 17, 33, 50, 100, 167, 233, 300, 500];
+const DEFAULT_ENTRY_DELAY: u16 = 0;
+
+const LINE_CLEAR_DELAY_OPTIONS: [u16; 8] =
+	[0, 100, 167, 233, 333, 500, 667, 1000];
+const DEFAULT_LINE_CLEAR_DELAY: u16 = 333;
+
+fn default_das() -> u16 {
+	DEFAULT_DAS
+}
+
+fn default_arr() -> u16 {
+	DEFAULT_ARR
+}
+
+fn default_soft_drop() -> u8 {
+	DEFAULT_SOFT_DROP
+}
+
+fn default_lock_delay() -> u16 {
+	DEFAULT_LOCK_DELAY
+}
+
+fn default_lock_reset_limit() -> u8 {
+	DEFAULT_LOCK_RESET_LIMIT
+}
+
+fn default_board_cols() -> u8 {
+	MAIN_BOARD_COLS as u8
+}
+
+fn default_board_visible_rows() -> u8 {
+	MAIN_BOARD_VISIBLE_ROWS as u8
+}
+
+fn default_sprint_lines() -> u16 {
+	DEFAULT_SPRINT_LINES
+}
+
+fn default_ultra_minutes() -> u8 {
+	DEFAULT_ULTRA_MINUTES
+}
+
+fn default_line_clear_delay() -> u16 {
+	DEFAULT_LINE_CLEAR_DELAY
+}
+
+fn default_practice_gravity() -> bool {
+	true
+}
+
+fn default_start_level() -> u8 {
+	1
+}
+
+fn default_dig_rows() -> u8 {
+	DEFAULT_DIG_ROWS
+}
+
+fn default_dig_messiness() -> u8 {
+	DEFAULT_DIG_MESSINESS
+}
+
+/// Step `value` within `0..=max`, wrapping around at both ends
+fn cycle(value: usize, max: usize, forward: bool) -> usize {
+	if forward {
+		if value >= max {
+			0
+		} else {
+			value + 1
+		}
+	} else if value == 0 {
+		max
+	} else {
+		value - 1
+	}
+}
+
+/// Step to the neighbouring option, values that are not in `options` start
+/// from the closest one above them
+fn cycle_option<T: Copy + PartialOrd>(
+	options: &[T],
+	value: T,
+	forward: bool,
+) -> T {
+	let idx = options.iter().position(|v| *v >= value).unwrap_or(0);
+
+	if options[idx] != value {
+		return options[idx];
+	}
+
+	options[cycle(idx, options.len() - 1, forward)]
+}
+
+/// The closest option above `value`, or the last option, for values read
+/// from the save that are not in `options`
+fn snap_option<T: Copy + PartialOrd>(options: &[T], value: T) -> T {
+	options
+		.iter()
+		.copied()
+		.find(|v| *v >= value)
+		.unwrap_or(options[options.len() - 1])
 }
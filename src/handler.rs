@@ -7,12 +7,18 @@ use tokio::{
 		mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
 	},
 	task::JoinSet,
-	time::{interval, sleep, Duration, Instant, Interval},
+	time::{
+		interval, interval_at, sleep, sleep_until, Duration, Instant, Interval,
+	},
 };
 
 use crate::{
 	consts::FRAME_RATE_SECS,
-	global::{is_locked, is_paused, set_locked, set_paused},
+	global::{
+		global_setting, is_key_release_supported, is_locked, is_paused,
+		set_locked, set_paused,
+		setting::{GravityCurve, LockReset, SONIC_DROP},
+	},
 };
 
 type Sender = UnboundedSender<Event>;
@@ -29,17 +35,44 @@ pub enum Event {
 	Down,
 	Left,
 	Right,
+	DownRelease,
+	LeftToWall,
+	RightToWall,
 	Space,
 	Enter,
 	Esc,
 	P,
 	Z,
-	Gravity,
+	A,
+	C,
+	U,
+	R,
+	S,
+	O,
+	/// Rows to fall
+	Gravity(u8),
+	/// Rows to fall while the soft drop key is held
+	SoftDrop(u8),
 	LockEnd,
 	CountDown,
 	Blink,
 }
 
+impl Event {
+	/// The event with left and right swapped, for the mirror modifier
+	pub fn mirrored(self) -> Self {
+		match self {
+			Event::Left => Event::Right,
+			Event::Right => Event::Left,
+			Event::LeftToWall => Event::RightToWall,
+			Event::RightToWall => Event::LeftToWall,
+			Event::Up => Event::Z,
+			Event::Z => Event::Up,
+			event => event,
+		}
+	}
+}
+
 #[derive(Clone, Debug)]
 enum SubEvent {
 	Pause,
@@ -47,6 +80,7 @@ enum SubEvent {
 	GravityReset,
 	GravityCancel,
 	Level(u32),
+	SoftDrop(bool),
 	LockCancel,
 	LockRefresh,
 }
@@ -91,6 +125,16 @@ pub struct SubHandler {
 	sub_tx: SubSender,
 	_sub_rx: SubReceiver,
 	set: JoinSet<()>,
+	lock: LockDelay,
+}
+
+/// Lock delay left to the alive tetromino, kept here so that lifting the
+/// piece off the ground does not give it a fresh delay
+struct LockDelay {
+	remaining: Duration,
+	resets: u8,
+	started_instant: Instant,
+	paused_instant: Instant,
 }
 
 impl SubHandler {
@@ -102,6 +146,12 @@ impl SubHandler {
 			sub_tx,
 			_sub_rx,
 			set: JoinSet::new(),
+			lock: LockDelay {
+				remaining: lock_delay(),
+				resets: 0,
+				started_instant: Instant::now(),
+				paused_instant: Instant::now(),
+			},
 		}
 	}
 
@@ -113,15 +163,33 @@ impl SubHandler {
 		self.set.spawn(count_down_task(self.tx.clone(), cnt));
 	}
 
-	pub fn spawn_gravity(&mut self) {
-		self.set
-			.spawn(gravity_task(self.tx.clone(), self.sub_tx.subscribe()));
+	pub fn spawn_gravity(&mut self, level: u32) {
+		self.set.spawn(gravity_task(
+			self.tx.clone(),
+			self.sub_tx.subscribe(),
+			level,
+		));
 	}
 
 	pub fn start_lock(&mut self) {
 		set_locked(true);
-		self.set
-			.spawn(lock_task(self.tx.clone(), self.sub_tx.subscribe()));
+		self.lock.started_instant = Instant::now();
+		self.set.spawn(lock_task(
+			self.tx.clone(),
+			self.sub_tx.subscribe(),
+			self.lock.remaining,
+		));
+	}
+
+	/// Give the full lock delay back, called when the piece spawns or
+	/// reaches a new lowest row
+	pub fn reset_lock(&mut self) {
+		self.lock.remaining = lock_delay();
+		self.lock.resets = 0;
+		if is_locked() {
+			self.lock.started_instant = Instant::now();
+			self.send(SubEvent::LockRefresh);
+		}
 	}
 
 	fn send(&self, event: SubEvent) {
@@ -140,21 +208,54 @@ impl SubHandler {
 		self.send(SubEvent::Level(level));
 	}
 
-	pub fn cancel_lock(&self) {
+	pub fn start_soft_drop(&self) {
+		self.send(SubEvent::SoftDrop(true));
+	}
+
+	pub fn stop_soft_drop(&self) {
+		self.send(SubEvent::SoftDrop(false));
+	}
+
+	pub fn cancel_lock(&mut self) {
+		if is_locked() {
+			self.lock.remaining = self
+				.lock
+				.remaining
+				.saturating_sub(self.lock.started_instant.elapsed());
+		}
 		set_locked(false);
 		self.send(SubEvent::LockCancel);
 	}
 
-	pub fn refresh_lock(&self) {
-		self.send(SubEvent::LockRefresh);
+	/// Called when the piece moves or rotates while the lock delay is
+	/// running
+	pub fn refresh_lock(&mut self) {
+		let setting = global_setting();
+
+		let can_reset = match setting.lock_reset() {
+			LockReset::Move => self.lock.resets < setting.lock_reset_limit(),
+			LockReset::Step => false,
+			LockReset::Infinite => true,
+		};
+
+		if can_reset {
+			self.lock.remaining = lock_delay();
+			self.lock.resets = self.lock.resets.saturating_add(1);
+			self.lock.started_instant = Instant::now();
+			self.send(SubEvent::LockRefresh);
+		}
 	}
 
 	pub fn pause(&mut self) {
 		set_paused(true);
+		self.lock.paused_instant = Instant::now();
 		self.send(SubEvent::Pause);
 	}
 
-	pub fn cancel_pause(&self) {
+	pub fn cancel_pause(&mut self) {
+		if is_paused() && is_locked() {
+			self.lock.started_instant += self.lock.paused_instant.elapsed();
+		}
 		set_paused(false);
 		self.send(SubEvent::PauseCancel);
 	}
@@ -173,58 +274,223 @@ async fn term_task(tx: Sender) {
 	use futures_util::StreamExt;
 
 	let mut event_stream = EventStream::new();
+	let mut auto_shift = AutoShift::new();
+	let mut is_down_held = false;
 
 	let space_throttle_ms = Duration::from_millis(200);
 	let mut space_instant = Instant::now();
 	let mut is_last_key_space = false;
 
-	while let Some(Ok(event)) = event_stream.next().await {
-		let game_event = match event {
-			TermEvent::Key(key) if key.kind == KeyEventKind::Press => {
-				let e = match key.code {
-					KeyCode::Char('c')
-						if key.modifiers == KeyModifiers::CONTROL =>
-					{
-						Event::CtrlC
+	loop {
+		let game_event = tokio::select! {
+			maybe_event = event_stream.next() => {
+				let Some(Ok(event)) = maybe_event else {
+					break;
+				};
+
+				match event {
+					TermEvent::Key(key) if key.kind == KeyEventKind::Release => {
+						if let Some(shift) = Shift::from_key_code(key.code) {
+							auto_shift.release(shift);
+							continue;
+						}
+						match key.code {
+							KeyCode::Down | KeyCode::Char('k') => {
+								is_down_held = false;
+								Event::DownRelease
+							}
+							_ => continue,
+						}
 					}
-					KeyCode::Up | KeyCode::Char('i') => Event::Up,
-					KeyCode::Down | KeyCode::Char('k') => Event::Down,
-					KeyCode::Left | KeyCode::Char('j') => Event::Left,
-					KeyCode::Right | KeyCode::Char('l') => Event::Right,
-					KeyCode::Enter => Event::Enter,
-					KeyCode::Char(' ') => {
-						if is_last_key_space
-							&& Instant::now() - space_instant
-								< space_throttle_ms
-						{
+					TermEvent::Key(key) => {
+						let e = match key.code {
+							KeyCode::Char('c')
+								if key.modifiers == KeyModifiers::CONTROL =>
+							{
+								Event::CtrlC
+							}
+							KeyCode::Up | KeyCode::Char('i') => Event::Up,
+							KeyCode::Down | KeyCode::Char('k') => {
+								if is_key_release_supported() {
+									if is_down_held {
+										continue;
+									}
+									is_down_held = true;
+								}
+								Event::Down
+							}
+							KeyCode::Left | KeyCode::Char('j') => {
+								if !auto_shift.press(Shift::Left) {
+									continue;
+								}
+								Event::Left
+							}
+							KeyCode::Right | KeyCode::Char('l') => {
+								if !auto_shift.press(Shift::Right) {
+									continue;
+								}
+								Event::Right
+							}
+							KeyCode::Enter => Event::Enter,
+							KeyCode::Char(' ') => {
+								if is_last_key_space
+									&& Instant::now() - space_instant
+										< space_throttle_ms
+								{
+									space_instant = Instant::now();
+									continue;
+								}
+
+								Event::Space
+							}
+							KeyCode::Esc => Event::Esc,
+							KeyCode::Char('p') => Event::P,
+							KeyCode::Char('z') => Event::Z,
+							KeyCode::Char('a') => Event::A,
+							KeyCode::Char('c') => Event::C,
+							KeyCode::Char('u') => Event::U,
+							KeyCode::Char('r') => Event::R,
+							KeyCode::Char('s') => Event::S,
+							KeyCode::Char('o') => Event::O,
+							_ => continue,
+						};
+
+						if e == Event::Space {
+							is_last_key_space = true;
 							space_instant = Instant::now();
-							continue;
+						} else {
+							is_last_key_space = false;
 						}
 
-						Event::Space
+						e
+					}
+					TermEvent::FocusLost => {
+						auto_shift.clear();
+						is_down_held = false;
+						Event::FocusLost
 					}
-					KeyCode::Esc => Event::Esc,
-					KeyCode::Char('p') => Event::P,
-					KeyCode::Char('z') => Event::Z,
 					_ => continue,
-				};
-
-				if e == Event::Space {
-					is_last_key_space = true;
-					space_instant = Instant::now();
-				} else {
-					is_last_key_space = false;
 				}
-
-				e
 			}
-			TermEvent::FocusLost => Event::FocusLost,
-			_ => continue,
+			_ = sleep_until(auto_shift.deadline), if auto_shift.is_active() => {
+				auto_shift.next_event()
+			}
 		};
 		tx.send(game_event).unwrap();
 	}
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Shift {
+	Left,
+	Right,
+}
+
+impl Shift {
+	fn from_key_code(code: KeyCode) -> Option<Self> {
+		match code {
+			KeyCode::Left | KeyCode::Char('j') => Some(Shift::Left),
+			KeyCode::Right | KeyCode::Char('l') => Some(Shift::Right),
+			_ => None,
+		}
+	}
+}
+
+/// Delayed auto shift driven by our own timer instead of the key repeat of
+/// the OS, only enabled when the terminal reports key releases
+struct AutoShift {
+	left: bool,
+	right: bool,
+	current: Option<Shift>,
+	deadline: Instant,
+}
+
+impl AutoShift {
+	fn new() -> Self {
+		Self {
+			left: false,
+			right: false,
+			current: None,
+			deadline: Instant::now(),
+		}
+	}
+
+	/// Returns `false` if the key is already held, which means the event is
+	/// a key repeat
+	fn press(&mut self, shift: Shift) -> bool {
+		if !is_key_release_supported() {
+			return true;
+		}
+
+		let held = match shift {
+			Shift::Left => &mut self.left,
+			Shift::Right => &mut self.right,
+		};
+
+		if *held {
+			return false;
+		}
+
+		*held = true;
+		self.charge(shift);
+
+		true
+	}
+
+	fn release(&mut self, shift: Shift) {
+		match shift {
+			Shift::Left => self.left = false,
+			Shift::Right => self.right = false,
+		}
+
+		if self.current != Some(shift) {
+			return;
+		}
+
+		self.current = None;
+
+		// Fall back to the direction that is still held
+		if shift == Shift::Left && self.right {
+			self.charge(Shift::Right);
+		} else if shift == Shift::Right && self.left {
+			self.charge(Shift::Left);
+		}
+	}
+
+	fn clear(&mut self) {
+		*self = Self::new();
+	}
+
+	fn charge(&mut self, shift: Shift) {
+		self.current = Some(shift);
+		self.deadline = Instant::now()
+			+ Duration::from_millis(global_setting().das() as u64);
+	}
+
+	fn is_active(&self) -> bool {
+		self.current.is_some()
+	}
+
+	fn next_event(&mut self) -> Event {
+		let arr = global_setting().arr();
+
+		if arr == 0 {
+			self.deadline =
+				Instant::now() + Duration::from_secs_f32(FRAME_RATE_SECS);
+			match self.current {
+				Some(Shift::Right) => Event::RightToWall,
+				_ => Event::LeftToWall,
+			}
+		} else {
+			self.deadline = Instant::now() + Duration::from_millis(arr as u64);
+			match self.current {
+				Some(Shift::Right) => Event::Right,
+				_ => Event::Left,
+			}
+		}
+	}
+}
+
 async fn count_down_task(tx: Sender, cnt: u8) {
 	for _ in 0..cnt {
 		sleep(Duration::from_secs(1)).await;
@@ -232,14 +498,13 @@ async fn count_down_task(tx: Sender, cnt: u8) {
 	}
 }
 
-async fn gravity_task(tx: Sender, mut sub_rx: SubReceiver) {
-	const MAX_GRAVITY_LEVEL: u32 = 15;
-
+async fn gravity_task(tx: Sender, mut sub_rx: SubReceiver, mut level: u32) {
 	let mut paused_instant = Instant::now();
 
-	let mut level = 1;
+	let mut soft_drop = false;
 
-	let mut gravity_interval = interval(gravity_duration(level));
+	let mut fall = Fall::new(level, soft_drop);
+	let mut gravity_interval = interval(fall.period);
 	let mut gravity_instant = Instant::now();
 
 	gravity_interval.reset();
@@ -267,9 +532,17 @@ async fn gravity_task(tx: Sender, mut sub_rx: SubReceiver) {
 					}
 					SubEvent::Level(lv) => {
 						level = lv;
-						if level <= MAX_GRAVITY_LEVEL {
-							gravity_interval = interval(gravity_duration(level));
+						fall = Fall::new(level, soft_drop);
+						gravity_interval = interval(fall.period);
+						gravity_interval.reset();
+					}
+					SubEvent::SoftDrop(v) => {
+						if soft_drop != v {
+							soft_drop = v;
+							fall = Fall::new(level, soft_drop);
+							gravity_interval = interval(fall.period);
 							gravity_interval.reset();
+							gravity_instant = Instant::now();
 						}
 					}
 					_ => (),
@@ -280,24 +553,34 @@ async fn gravity_task(tx: Sender, mut sub_rx: SubReceiver) {
 					continue;
 				}
 				gravity_instant = Instant::now();
-				tx.send(Event::Gravity).unwrap();
+				let rows = fall.next_rows();
+				if rows == 0 {
+					continue;
+				}
+				let event = if soft_drop {
+					Event::SoftDrop(rows)
+				} else {
+					Event::Gravity(rows)
+				};
+				tx.send(event).unwrap();
 			}
 		}
 	}
 }
 
-async fn lock_task(tx: Sender, mut sub_rx: SubReceiver) {
+async fn lock_task(tx: Sender, mut sub_rx: SubReceiver, remaining: Duration) {
 	let mut paused_instant = Instant::now();
 
-	let mut lock_limit = 15;
-	let mut lock_interval = interval(Duration::from_millis(500));
-	let mut lock_instant = Instant::now();
+	let delay = lock_delay();
+	let remaining = remaining.min(delay);
+	let mut lock_interval = interval_at(Instant::now() + remaining, delay);
+	// Pretend the interval started earlier so that pausing keeps the
+	// remaining time
+	let mut lock_instant = Instant::now() + remaining - delay;
 
 	let mut blink_interval = interval(Duration::from_millis(150));
 	let mut blink_instant = Instant::now();
 
-	lock_interval.reset();
-
 	loop {
 		tokio::select! {
 			Ok(event) = sub_rx.recv() => {
@@ -321,11 +604,8 @@ async fn lock_task(tx: Sender, mut sub_rx: SubReceiver) {
 						break;
 					}
 					SubEvent::LockRefresh => {
-						if lock_limit > 0 {
-							lock_limit -= 1;
-							lock_interval.reset();
-							lock_instant = Instant::now();
-						}
+						lock_interval.reset();
+						lock_instant = Instant::now();
 					}
 					_ => (),
 				}
@@ -349,13 +629,133 @@ async fn lock_task(tx: Sender, mut sub_rx: SubReceiver) {
 	}
 }
 
-fn gravity_duration(level: u32) -> Duration {
-	let base = (level - 1) as f32;
-	let duration_secs = (0.8 - base * 0.007).powf(base);
+fn lock_delay() -> Duration {
+	Duration::from_millis(global_setting().lock_delay() as u64)
+}
+
+/// Gravity is measured in rows per frame of a 60 Hz game
+const GRAVITY_FRAME_SECS: f64 = 1.0 / 60.0;
+
+/// 20G, the piece reaches the bottom within a single frame
+const MAX_GRAVITY: f64 = 20.0;
 
-	Duration::from_secs_f32(duration_secs)
+/// How fast the alive tetromino falls, in rows every `period`
+struct Fall {
+	period: Duration,
+	rows_per_tick: f64,
+	rows: f64,
 }
 
+impl Fall {
+	fn new(level: u32, soft_drop: bool) -> Self {
+		let factor = global_setting().soft_drop();
+
+		let gravity = if !soft_drop {
+			gravity(level)
+		} else if factor == SONIC_DROP {
+			// Keep dropping newly spawned pieces while the key is held
+			MAX_GRAVITY
+		} else {
+			gravity(level) * factor as f64
+		}
+		.min(MAX_GRAVITY);
+
+		// Below 1G the piece falls a row every few frames, above it falls
+		// several rows every frame
+		if gravity < 1.0 {
+			Self {
+				period: Duration::from_secs_f64(GRAVITY_FRAME_SECS / gravity),
+				rows_per_tick: 1.0,
+				rows: 0.0,
+			}
+		} else {
+			Self {
+				period: Duration::from_secs_f64(GRAVITY_FRAME_SECS),
+				rows_per_tick: gravity,
+				rows: 0.0,
+			}
+		}
+	}
+
+	/// Whole rows to fall on this tick, the fraction is carried over
+	fn next_rows(&mut self) -> u8 {
+		self.rows += self.rows_per_tick;
+		let rows = self.rows.floor();
+		self.rows -= rows;
+
+		rows as u8
+	}
+}
+
+/// Rows per frame at the given level
+fn gravity(level: u32) -> f64 {
+	let level = level.max(1);
+
+	match global_setting().gravity() {
+		GravityCurve::Guideline => {
+			// Seconds per row, reaches 20G around level 20
+			let base = (level.min(20) - 1) as f64;
+			let secs = (0.8 - base * 0.007).powf(base);
+			GRAVITY_FRAME_SECS / secs
+		}
+		GravityCurve::Nes => {
+			let idx = (level as usize - 1).min(NES_FRAMES_PER_ROW.len() - 1);
+			1.0 / NES_FRAMES_PER_ROW[idx] as f64
+		}
+		GravityCurve::Tgm => {
+			// Each level is worth 25 internal levels, so 20G shows up at
+			// level 21
+			let tgm_level = (level - 1) * 25;
+			let internal = TGM_INTERNAL_GRAVITY
+				.iter()
+				.rev()
+				.find(|(lv, _)| tgm_level >= *lv)
+				.map_or(4, |(_, g)| *g);
+			internal as f64 / 256.0
+		}
+	}
+}
+
+/// NTSC frames per row from level 0 to 29
+const NES_FRAMES_PER_ROW: [u8; 30] = [
+	48, 43, 38, 33, 28, 23, 18, 13, 8, 6, 5, 5, 5, 4, 4, 4, 3, 3, 3, 2, 2, 2,
+	2, 2, 2, 2, 2, 2, 2, 1,
+];
+
+/// Internal gravity in 1/256 rows per frame, keyed by the starting level
+const TGM_INTERNAL_GRAVITY: [(u32, u32); 30] = [
+	(0, 4),
+	(30, 6),
+	(35, 8),
+	(40, 10),
+	(50, 12),
+	(60, 16),
+	(70, 32),
+	(80, 48),
+	(90, 64),
+	(100, 80),
+	(120, 96),
+	(140, 112),
+	(160, 128),
+	(170, 144),
+	(200, 4),
+	(220, 32),
+	(230, 64),
+	(233, 96),
+	(236, 128),
+	(239, 160),
+	(243, 192),
+	(247, 224),
+	(251, 256),
+	(300, 512),
+	(330, 768),
+	(360, 1024),
+	(400, 1280),
+	(420, 1024),
+	(450, 768),
+	(500, 5120),
+];
+
 fn make_time_continue(
 	interval: &mut Interval,
 	paused_instant: &Instant,
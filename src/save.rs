@@ -1,4 +1,9 @@
-use std::{fs, path::PathBuf};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	fs,
+	path::{Path, PathBuf},
+	time::Duration,
+};
 
 use anyhow::Result;
 use directories::ProjectDirs;
@@ -6,28 +11,54 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
 	consts::APP_NAME,
-	global::{global_setting, setting::SettingSave},
+	global::{global_setting, set_board_size, setting::SettingSave},
 	state::{
-		bag::Bag, focus::Scene, main_board::MainBoard, next_board::NextBoard,
-		scores::Scores, stats::Stats, tetromino::Tetromino, State,
+		bag::Bag,
+		game_mode::GameMode,
+		garbage::Garbage,
+		hold_board::HoldBoard,
+		main_board::MainBoard,
+		modifiers::Modifiers,
+		next_board::NextBoard,
+		puzzle::{Puzzle, Setup},
+		scores::Leaderboards,
+		stats::Stats,
+		stopwatch::Stopwatch,
+		tetromino::Tetromino,
+		State,
 	},
 };
 
 const SAVE_FILE: &str = "save.json";
 
+const PUZZLE_DIR: &str = "puzzles";
+
+const BOARD_DIR: &str = "boards";
+
 pub struct Save {
 	inner: Option<SaveInner>,
 }
 
 struct SaveInner {
 	pub file: PathBuf,
+	pub puzzle_dir: PathBuf,
+	/// Boards saved by the board editor
+	pub board_dir: PathBuf,
 	pub content: SaveContent,
 }
 
 #[derive(Deserialize, Serialize)]
 struct SaveContent {
 	setting: SettingSave,
-	scores: Scores,
+	#[serde(flatten)]
+	leaderboards: Leaderboards,
+	/// Leaderboards of games with modifiers or another scoring, by the tag
+	/// of the modifiers and the scoring system
+	#[serde(default)]
+	modified_leaderboards: BTreeMap<String, Leaderboards>,
+	/// Ids of the solved puzzles
+	#[serde(default)]
+	solved_puzzles: BTreeSet<String>,
 	last_game: Option<LastGame>,
 }
 
@@ -35,9 +66,25 @@ struct SaveContent {
 struct LastGame {
 	board: MainBoard,
 	next_board: NextBoard,
+	#[serde(default = "HoldBoard::new")]
+	hold_board: HoldBoard,
 	bag: Bag,
 	stats: Stats,
 	alive_tetromino: Tetromino,
+	#[serde(default)]
+	mode: GameMode,
+	#[serde(default)]
+	stopwatch: Stopwatch,
+	#[serde(default)]
+	garbage: Garbage,
+	#[serde(default)]
+	puzzle: Option<Puzzle>,
+	#[serde(default)]
+	modifiers: Modifiers,
+	#[serde(default)]
+	spawn_at: Option<Duration>,
+	#[serde(default)]
+	custom_setup: Option<Setup>,
 }
 
 impl Save {
@@ -78,9 +125,13 @@ impl SaveInner {
 		};
 
 		let file = dir.join(SAVE_FILE);
+		let puzzle_dir = dir.join(PUZZLE_DIR);
+		let board_dir = dir.join(BOARD_DIR);
 
-		if !dir.exists() {
-			fs::create_dir_all(dir)?;
+		for dir in [&puzzle_dir, &board_dir] {
+			if !dir.exists() {
+				fs::create_dir_all(dir)?;
+			}
 		}
 
 		if !file.exists() {
@@ -89,9 +140,13 @@ impl SaveInner {
 
 		Ok(Self {
 			file,
+			puzzle_dir,
+			board_dir,
 			content: SaveContent {
 				setting: SettingSave::default(),
-				scores: Scores::new(),
+				leaderboards: Leaderboards::default(),
+				modified_leaderboards: BTreeMap::new(),
+				solved_puzzles: BTreeSet::new(),
 				last_game: None,
 			},
 		})
@@ -112,38 +167,118 @@ impl SaveInner {
 	}
 
 	fn read(&mut self, state: &mut State) {
+		(state.puzzle_menu.puzzles, state.puzzle_menu.errors) =
+			read_puzzles(&self.puzzle_dir);
+		state.puzzle_menu.dir = Some(self.puzzle_dir.clone());
+		state.editor.dir = Some(self.board_dir.clone());
+
 		if self.try_read().is_ok() {
-			state.scores.clone_from(&self.content.scores);
+			state.leaderboards.clone_from(&self.content.leaderboards);
+			state
+				.modified_leaderboards
+				.clone_from(&self.content.modified_leaderboards);
+			state
+				.puzzle_menu
+				.solved
+				.clone_from(&self.content.solved_puzzles);
 			global_setting().read_from_save(&self.content.setting);
 			let Some(last_game) = self.content.last_game.take() else {
 				return;
 			};
 			state.count_down = 3;
+			set_board_size(
+				last_game.board.cols(),
+				last_game.board.visible_rows(),
+			);
 			state.board.replace(last_game.board);
-			state.next_board.clone_from(&last_game.next_board);
 			state.bag.clone_from(&last_game.bag);
+			state.next_board.clone_from(&last_game.next_board);
+			state.next_board.fill(&mut state.bag);
+			state.hold_board.clone_from(&last_game.hold_board);
 			state.stats.clone_from(&last_game.stats);
 			state.alive_tetromino.clone_from(&last_game.alive_tetromino);
 			state.alive_tetromino.set_board(state.board.clone());
+			state.mode = last_game.mode;
+			state.stopwatch.clone_from(&last_game.stopwatch);
+			state.garbage.clone_from(&last_game.garbage);
+			state.puzzle.clone_from(&last_game.puzzle);
+			state.modifiers = last_game.modifiers;
+			state.spawn_at = last_game.spawn_at;
+			state.custom_setup = last_game.custom_setup;
 		}
 	}
 
 	fn write(&mut self, state: &State) {
-		self.content.scores = state.scores.clone();
+		self.content.leaderboards = state.leaderboards.clone();
+		self.content.modified_leaderboards =
+			state.modified_leaderboards.clone();
+		self.content.solved_puzzles = state.puzzle_menu.solved.clone();
 		self.content.setting = global_setting().to_save_content();
-		self.content.last_game =
-			if *state.focus.current() != Scene::GameOverMenu {
-				Some(LastGame {
-					board: state.board.borrow().clone(),
-					next_board: state.next_board.clone(),
-					bag: state.bag.clone(),
-					alive_tetromino: state.alive_tetromino.clone(),
-					stats: state.stats.clone(),
-				})
-			} else {
-				None
-			};
+		self.content.last_game = if state.has_game() {
+			Some(LastGame {
+				board: state.board.borrow().clone(),
+				next_board: state.next_board.clone(),
+				hold_board: state.hold_board.clone(),
+				bag: state.bag.clone(),
+				alive_tetromino: state.alive_tetromino.clone(),
+				stats: state.stats.clone(),
+				mode: state.mode,
+				stopwatch: state.stopwatch.clone(),
+				garbage: state.garbage.clone(),
+				puzzle: state.puzzle.clone(),
+				modifiers: state.modifiers,
+				spawn_at: state.spawn_at,
+				custom_setup: state.custom_setup.clone(),
+			})
+		} else {
+			None
+		};
 
 		let _ = self.try_write();
 	}
 }
+
+/// Returns the puzzles and the files that fail to parse, as
+/// `file name: reason`
+fn read_puzzles(dir: &Path) -> (Vec<Puzzle>, Vec<String>) {
+	let Ok(entries) = fs::read_dir(dir) else {
+		return (Vec::new(), Vec::new());
+	};
+
+	let mut puzzles = Vec::new();
+	let mut errors = Vec::new();
+
+	for entry in entries.flatten() {
+		let path = entry.path();
+		let (Some(id), Some(name)) = (
+			path.file_stem().and_then(|id| id.to_str()),
+			path.file_name().and_then(|name| name.to_str()),
+		) else {
+			continue;
+		};
+
+		let puzzle = match path.extension().and_then(|ext| ext.to_str()) {
+			Some("json") => {
+				fs::read_to_string(&path)
+					.map_err(Into::into)
+					.and_then(|content| Puzzle::from_json(id, &content))
+			}
+			Some("toml") => {
+				fs::read_to_string(&path)
+					.map_err(Into::into)
+					.and_then(|content| Puzzle::from_toml(id, &content))
+			}
+			_ => continue,
+		};
+
+		match puzzle {
+			Ok(puzzle) => puzzles.push(puzzle),
+			Err(e) => errors.push(format!("{}: {}", name, e)),
+		}
+	}
+
+	puzzles.sort_by(|a, b| a.id.cmp(&b.id));
+	errors.sort();
+
+	(puzzles, errors)
+}
@@ -1,54 +1,94 @@
+use std::mem;
+
+use fastrand::Rng;
 use serde::{Deserialize, Serialize};
 
-use crate::common::{Reset, TetrominoKind};
+use super::randomizer::Randomizer;
+use crate::{
+	common::{Reset, TetrominoKind},
+	global::global_setting,
+};
 
 #[derive(Clone, Deserialize, Serialize)]
 pub struct Bag {
-	kinds: Vec<TetrominoKind>,
-	cursor: usize,
-	last: Option<TetrominoKind>,
+	#[serde(default)]
+	randomizer: Randomizer,
+	/// Seed the game started with, the same seed deals the same pieces
+	#[serde(default = "random_seed")]
+	seed: u64,
+	#[serde(default = "Rng::new", with = "rng_state")]
+	rng: Rng,
 }
 
 impl Bag {
 	pub fn new() -> Self {
+		let seed = random_seed();
+
 		Self {
-			kinds: vec![
-				TetrominoKind::I,
-				TetrominoKind::J,
-				TetrominoKind::L,
-				TetrominoKind::O,
-				TetrominoKind::S,
-				TetrominoKind::T,
-				TetrominoKind::Z,
-			],
-			cursor: 0,
-			last: None,
+			randomizer: Randomizer::default(),
+			seed,
+			rng: Rng::with_seed(seed),
 		}
 	}
 
-	fn shuffle(&mut self) {
-		self.cursor = 0;
-		fastrand::shuffle(self.kinds.as_mut_slice());
-		if self.last.is_some_and(|last| last == self.kinds[0]) {
-			self.kinds.swap(0, 1);
-		}
+	pub fn seed(&self) -> u64 {
+		self.seed
 	}
 
-	pub fn next(&mut self) -> TetrominoKind {
-		if self.cursor >= self.kinds.len() {
-			self.shuffle();
-		}
-		let kind = self.kinds[self.cursor];
-		self.cursor += 1;
-		self.last = Some(kind);
+	/// Start over with a known seed and the randomizer from the setting
+	pub fn reseed(&mut self, seed: u64) {
+		self.randomizer = Randomizer::new(global_setting().randomizer());
+		self.seed = seed;
+		self.rng.seed(seed);
+	}
+
+	/// Deal `kinds` in order before the randomizer, or instead of it when
+	/// the bag should run out after them
+	pub fn deal_sequence(&mut self, kinds: Vec<TetrominoKind>, run_out: bool) {
+		let then = if run_out {
+			None
+		} else {
+			Some(Box::new(mem::take(&mut self.randomizer)))
+		};
+
+		self.randomizer = Randomizer::Sequence {
+			kinds,
+			cursor: 0,
+			then,
+		};
+	}
 
-		kind
+	pub fn next(&mut self) -> Option<TetrominoKind> {
+		self.randomizer.next(&mut self.rng)
 	}
 }
 
 impl Reset for Bag {
 	fn reset(&mut self) {
-		*self = Self::new();
-		self.shuffle();
+		self.reseed(random_seed());
+	}
+}
+
+fn random_seed() -> u64 {
+	fastrand::u64(..)
+}
+
+/// The generator is saved as its current state so that a resumed game deals
+/// the same pieces
+pub(super) mod rng_state {
+	use fastrand::Rng;
+	use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+	pub fn serialize<S: Serializer>(
+		rng: &Rng,
+		serializer: S,
+	) -> Result<S::Ok, S::Error> {
+		rng.get_seed().serialize(serializer)
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(
+		deserializer: D,
+	) -> Result<Rng, D::Error> {
+		u64::deserialize(deserializer).map(Rng::with_seed)
 	}
 }
@@ -0,0 +1,254 @@
+use std::{
+	collections::BTreeSet,
+	fs,
+	path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Result};
+
+use super::puzzle::{Setup, PUZZLE_FREE_ROWS};
+use crate::{common::TetrominoKind, global::global_setting};
+
+/// Paints a board and a piece sequence to start a game from, the boards are
+/// saved as puzzle files without the name and the objective
+pub struct Editor {
+	/// Rows from top to bottom, they sit at the bottom of the board
+	pub cells: Vec<Vec<Option<TetrominoKind>>>,
+	/// Pieces queued up for the game, the randomizer from the setting deals
+	/// the ones after them
+	pub pieces: Vec<TetrominoKind>,
+	pub cursor_x: usize,
+	pub cursor_y: usize,
+	/// Index in [`BRUSHES`]
+	brush: usize,
+	/// Where the board files are saved
+	pub dir: Option<PathBuf>,
+	/// The file last saved or loaded
+	pub file: Option<PathBuf>,
+	/// Files created by saving since the game started, saving writes to the
+	/// current file again only when it is one of them so that a loaded board
+	/// of someone else is kept
+	created: BTreeSet<PathBuf>,
+	/// Result of the last save, load or play
+	pub message: Option<String>,
+}
+
+/// Cells the brush paints, `None` erases
+const BRUSHES: [Option<TetrominoKind>; 9] = [
+	Some(TetrominoKind::I),
+	Some(TetrominoKind::J),
+	Some(TetrominoKind::L),
+	Some(TetrominoKind::O),
+	Some(TetrominoKind::S),
+	Some(TetrominoKind::T),
+	Some(TetrominoKind::Z),
+	Some(TetrominoKind::Garbage),
+	None,
+];
+
+impl Editor {
+	pub fn new() -> Self {
+		Self {
+			cells: Vec::new(),
+			pieces: Vec::new(),
+			cursor_x: 0,
+			cursor_y: 0,
+			brush: 0,
+			dir: None,
+			file: None,
+			created: BTreeSet::new(),
+			message: None,
+		}
+	}
+
+	/// The board takes the size from the setting, the top rows of the board
+	/// are kept free for the pieces to enter
+	pub fn open(&mut self) {
+		let setting = global_setting();
+
+		self.resize(
+			setting.board_cols(),
+			setting.board_visible_rows() - PUZZLE_FREE_ROWS,
+			self.cells.clone(),
+		);
+		self.message = None;
+	}
+
+	/// Cells that do not fit are dropped from the top and the right
+	fn resize(
+		&mut self,
+		cols: usize,
+		rows: usize,
+		cells: Vec<Vec<Option<TetrominoKind>>>,
+	) {
+		self.cells = vec![vec![None; cols]; rows];
+
+		for (line, row) in self.cells.iter_mut().rev().zip(cells.iter().rev()) {
+			for (cell, kind) in line.iter_mut().zip(row) {
+				*cell = *kind;
+			}
+		}
+
+		self.cursor_x = self.cursor_x.min(cols - 1);
+		self.cursor_y = self.cursor_y.min(rows - 1);
+	}
+
+	pub fn cols(&self) -> usize {
+		self.cells.first().map_or(0, |row| row.len())
+	}
+
+	pub fn rows(&self) -> usize {
+		self.cells.len()
+	}
+
+	pub fn move_cursor(&mut self, dx: isize, dy: isize) {
+		self.cursor_x =
+			self.cursor_x.saturating_add_signed(dx).min(self.cols() - 1);
+		self.cursor_y =
+			self.cursor_y.saturating_add_signed(dy).min(self.rows() - 1);
+	}
+
+	pub fn cursor_to_wall(&mut self, right: bool) {
+		self.cursor_x = if right {
+			self.cols() - 1
+		} else {
+			0
+		};
+	}
+
+	pub fn brush(&self) -> Option<TetrominoKind> {
+		BRUSHES[self.brush]
+	}
+
+	pub fn change_brush(&mut self, forward: bool) {
+		self.brush = if forward {
+			(self.brush + 1) % BRUSHES.len()
+		} else {
+			(self.brush + BRUSHES.len() - 1) % BRUSHES.len()
+		};
+	}
+
+	pub fn paint(&mut self) {
+		self.cells[self.cursor_y][self.cursor_x] = self.brush();
+	}
+
+	/// Garbage and the eraser are not pieces
+	pub fn add_piece(&mut self) {
+		match self.brush() {
+			Some(TetrominoKind::Garbage) | None => (),
+			Some(kind) => self.pieces.push(kind),
+		}
+	}
+
+	pub fn remove_piece(&mut self) {
+		self.pieces.pop();
+	}
+
+	/// The empty rows at the top are left out
+	pub fn setup(&self) -> Result<Setup> {
+		let start = self
+			.cells
+			.iter()
+			.position(|row| row.iter().any(|cell| cell.is_some()))
+			.unwrap_or(self.rows() - 1);
+
+		Setup::new(self.pieces.clone(), self.cells[start..].to_vec())
+	}
+
+	pub fn save(&mut self) {
+		self.message = Some(match self.try_save() {
+			Ok(name) => format!("SAVED {}", name),
+			Err(e) => e.to_string().to_uppercase(),
+		});
+	}
+
+	/// Loads the board file after the current one in the board directory,
+	/// the files that fail to parse on the way are named in the message
+	pub fn load_next(&mut self) {
+		let mut skipped = Vec::new();
+
+		let mut message = match self.try_load_next(&mut skipped) {
+			Ok(name) => format!("LOADED {}", name),
+			Err(e) => e.to_string().to_uppercase(),
+		};
+		if !skipped.is_empty() {
+			message.push_str(&format!(", SKIPPED {}", skipped.join(" ")));
+		}
+
+		self.message = Some(message);
+	}
+
+	/// Returns the file name
+	fn try_save(&mut self) -> Result<String> {
+		let setup = self.setup()?;
+		let dir = self.dir.as_ref().ok_or(anyhow!("no board directory"))?;
+
+		let file = match &self.file {
+			Some(file) if self.created.contains(file) => file.clone(),
+			_ => {
+				(1..)
+					.map(|n| dir.join(format!("board-{}.toml", n)))
+					.find(|file| !file.exists())
+					.expect("should be a free file name")
+			}
+		};
+
+		fs::write(&file, setup.to_toml())?;
+		let name = file_name(&file);
+		self.created.insert(file.clone());
+		self.file = Some(file);
+
+		Ok(name)
+	}
+
+	/// Names of the files that fail to parse go to `skipped`, returns the
+	/// file name
+	fn try_load_next(&mut self, skipped: &mut Vec<String>) -> Result<String> {
+		let dir = self.dir.as_ref().ok_or(anyhow!("no board directory"))?;
+
+		let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+			.filter_map(|entry| {
+				let path = entry.ok()?.path();
+				(path.extension()? == "toml").then_some(path)
+			})
+			.collect();
+		files.sort();
+
+		let start = self
+			.file
+			.as_ref()
+			.and_then(|file| files.iter().position(|f| f == file))
+			.map_or(0, |idx| idx + 1);
+
+		let (file, setup) = (0..files.len())
+			.map(|i| &files[(start + i) % files.len()])
+			.find_map(|file| {
+				let setup = fs::read_to_string(file)
+					.map_err(Into::into)
+					.and_then(|content| Setup::from_toml(&content));
+
+				match setup {
+					Ok(setup) => Some((file.clone(), setup)),
+					Err(_) => {
+						skipped.push(file_name(file));
+						None
+					}
+				}
+			})
+			.ok_or(anyhow!("no boards to load"))?;
+
+		let rows = (global_setting().board_visible_rows() - PUZZLE_FREE_ROWS)
+			.max(setup.cells.len());
+		self.resize(setup.board_size().0, rows, setup.cells);
+		self.pieces = setup.pieces;
+		let name = file_name(&file);
+		self.file = Some(file);
+
+		Ok(name)
+	}
+}
+
+fn file_name(file: &Path) -> String {
+	file.file_name()
+		.map_or(String::new(), |name| name.to_string_lossy().to_string())
+}
@@ -7,6 +7,10 @@ pub enum Scene {
 	PauseMenu,
 	GameOverMenu,
 	SettingMenu,
+	ModeMenu,
+	PuzzleMenu,
+	ModifierMenu,
+	Editor,
 	Scores,
 	Help,
 	About,
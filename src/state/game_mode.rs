@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+	puzzle::Objective, stats::Stats, stopwatch::Stopwatch, tetromino::TSpin,
+};
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum GameMode {
+	/// Won after level 15, which is [`MARATHON_LINES`] lines
+	#[default]
+	Marathon,
+	/// The game only ends on top out
+	Endless,
+	/// Clear `lines` lines as fast as possible
+	Sprint { lines: u32 },
+	/// Score as much as possible in `minutes` minutes
+	Ultra { minutes: u32 },
+	/// Dig through `rows` rows of garbage as fast as possible
+	Dig { rows: u32 },
+	/// A puzzle loaded from a file
+	Puzzle { objective: Objective },
+	/// Placements can be undone and redone, nothing is recorded
+	Practice { gravity: bool },
+}
+
+impl GameMode {
+	pub fn is_finished(&self, stats: &Stats, stopwatch: &Stopwatch) -> bool {
+		match self {
+			GameMode::Marathon => stats.lines >= MARATHON_LINES,
+			GameMode::Endless => false,
+			GameMode::Sprint {
+				lines,
+			} => stats.lines >= *lines,
+			GameMode::Ultra {
+				..
+			} => self.remaining_time(stopwatch) == Some(Duration::ZERO),
+			GameMode::Dig {
+				..
+			} => stats.garbage_left == 0,
+			GameMode::Puzzle {
+				objective,
+			} => {
+				match objective {
+					Objective::ClearAll => {
+						stats
+							.last_clear
+							.is_some_and(|clear| clear.perfect_clear)
+					}
+					Objective::TSpinDouble => {
+						stats.last_clear.is_some_and(|clear| {
+							clear.t_spin == TSpin::Full && clear.lines == 2
+						})
+					}
+					Objective::Survive(pieces) => stats.pieces >= *pieces,
+				}
+			}
+			GameMode::Practice {
+				..
+			} => false,
+		}
+	}
+
+	pub fn is_timed(&self) -> bool {
+		matches!(
+			self,
+			GameMode::Sprint { .. }
+				| GameMode::Ultra { .. }
+				| GameMode::Dig { .. }
+				| GameMode::Puzzle { .. }
+		)
+	}
+
+	/// Whether the game starts at the level chosen in the mode menu
+	pub fn has_start_level(&self) -> bool {
+		matches!(self, GameMode::Marathon | GameMode::Endless)
+	}
+
+	pub fn has_gravity(&self) -> bool {
+		!matches!(
+			self,
+			GameMode::Practice {
+				gravity: false
+			}
+		)
+	}
+
+	/// Time left for modes with a time limit
+	pub fn remaining_time(&self, stopwatch: &Stopwatch) -> Option<Duration> {
+		match self {
+			GameMode::Ultra {
+				minutes,
+			} => {
+				let limit = Duration::from_secs(*minutes as u64 * 60);
+				Some(limit.saturating_sub(stopwatch.elapsed()))
+			}
+			_ => None,
+		}
+	}
+}
+
+pub const MARATHON_LINES: u32 = 150;
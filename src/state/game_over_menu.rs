@@ -1,27 +1,136 @@
+use std::time::Duration;
+
+use super::stopwatch::format_time;
 use crate::common::{Menu, VecExt};
 
 pub struct GameOverMenu {
 	items: Vec<String>,
 	cursor: usize,
+	pub title: &'static str,
 	pub new_score: Option<String>,
+	pub new_score_title: &'static str,
+	/// Extra line below the title, such as the time against the best one
+	pub detail: Option<String>,
 }
 
 impl GameOverMenu {
 	pub fn new() -> Self {
 		Self {
-			items: vec!["NEW GAME", "SCORES", "QUIT"].into_owned_vec(),
+			items: vec!["NEW GAME", "SCORES", "MAIN MENU", "QUIT"]
+				.into_owned_vec(),
 			cursor: 0,
+			title: "GAME OVER",
 			new_score: None,
+			new_score_title: "NEW SCORE",
+			detail: None,
 		}
 	}
 
 	pub fn set_new_score(&mut self, score: u32, idx: Option<usize>) {
-		if let Some(i) = idx {
-			self.new_score = Some(format!("{}.{:>11}", i + 1, score));
-		} else {
-			self.new_score = None;
+		self.title = "GAME OVER";
+		self.new_score_title = "NEW SCORE";
+		self.detail = None;
+		self.new_score = idx.map(|i| format!("{}.{:>11}", i + 1, score));
+	}
+
+	/// Times are in milliseconds, `best` is the personal best before this
+	/// game
+	pub fn set_new_time(
+		&mut self,
+		time: u64,
+		idx: Option<usize>,
+		best: Option<u64>,
+	) {
+		let time_text = format_time(Duration::from_millis(time));
+
+		self.title = "FINISH";
+		self.new_score_title = "NEW TIME";
+		self.new_score = idx.map(|i| format!("{}.{:>11}", i + 1, time_text));
+		self.detail = Some(match best {
+			Some(best) if time < best => {
+				format!(
+					"TIME {}  PB -{}",
+					time_text,
+					format_time(Duration::from_millis(best - time))
+				)
+			}
+			Some(best) => {
+				format!(
+					"TIME {}  PB +{}",
+					time_text,
+					format_time(Duration::from_millis(time - best))
+				)
+			}
+			None => format!("TIME {}  NEW PB", time_text),
+		});
+	}
+
+	/// Same as [`GameOverMenu::set_new_time`] with the pieces used
+	pub fn set_new_dig_time(
+		&mut self,
+		time: u64,
+		pieces: u32,
+		idx: Option<usize>,
+		best: Option<u64>,
+	) {
+		self.set_new_time(time, idx, best);
+		if let Some(detail) = &mut self.detail {
+			detail.push_str(&format!("  {} PIECES", pieces));
 		}
 	}
+
+	/// The marathon goal is reached
+	pub fn set_victory(&mut self, score: u32, idx: Option<usize>) {
+		self.set_new_score(score, idx);
+		self.title = "VICTORY";
+		self.detail = Some(format!("SCORE {}", score));
+	}
+
+	/// Times are in milliseconds
+	pub fn set_puzzle_result(&mut self, solved: bool, pieces: u32, time: u64) {
+		self.title = if solved {
+			"SOLVED"
+		} else {
+			"FAILED"
+		};
+		self.new_score = None;
+		self.detail = Some(format!(
+			"PIECES {}  TIME {}",
+			pieces,
+			format_time(Duration::from_millis(time))
+		));
+	}
+
+	/// The time limit is reached
+	pub fn set_time_up(&mut self, score: u32, idx: Option<usize>) {
+		self.set_new_score(score, idx);
+		self.title = "TIME UP";
+		self.detail = Some(format!("SCORE {}", score));
+	}
+
+	/// Grade of the TGM scoring, shown after the other details
+	pub fn add_grade(&mut self, grade: &str) {
+		self.add_detail(format!("GRADE {}", grade));
+	}
+
+	/// Why the game was lost, shown after the other details
+	pub fn add_top_out(&mut self, top_out: TopOut) {
+		self.add_detail(top_out.text().to_string());
+	}
+
+	fn add_detail(&mut self, text: String) {
+		self.detail = Some(match self.detail.take() {
+			Some(detail) => format!("{}  {}", detail, text),
+			None => text,
+		});
+	}
+
+	/// Topped out before reaching the goal
+	pub fn set_no_record(&mut self) {
+		self.title = "GAME OVER";
+		self.new_score = None;
+		self.detail = None;
+	}
 }
 
 impl Menu for GameOverMenu {
@@ -42,8 +151,30 @@ impl Menu for GameOverMenu {
 	}
 }
 
+/// How the stack reached the top
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TopOut {
+	/// The next piece overlaps the stack where it spawns
+	Block,
+	/// A piece locked entirely above the visible rows
+	Lock,
+	/// Rising garbage pushed the stack out of the board
+	Garbage,
+}
+
+impl TopOut {
+	pub fn text(&self) -> &'static str {
+		match self {
+			TopOut::Block => "BLOCK OUT",
+			TopOut::Lock => "LOCK OUT",
+			TopOut::Garbage => "TOP OUT",
+		}
+	}
+}
+
 pub mod game_over_menu_idx {
 	pub const NEW_GAME: usize = 0;
 	pub const SCORES: usize = 1;
-	pub const QUIT: usize = 2;
+	pub const MAIN_MENU: usize = 2;
+	pub const QUIT: usize = 3;
 }
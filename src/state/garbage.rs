@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use fastrand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::bag::rng_state;
+use crate::{common::TetrominoKind, global::global_setting};
+
+/// Deals the garbage rows of the dig mode
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Garbage {
+	#[serde(with = "rng_state")]
+	rng: Rng,
+	/// Column of the hole in the last dealt row
+	hole: Option<usize>,
+	/// Rows waiting to rise, they rise once the current piece locks so
+	/// that they never push into it
+	pending: usize,
+	/// Game time when the next rising row is queued
+	next_at: Duration,
+}
+
+impl Garbage {
+	/// The generator is derived from the game seed, so the same seed deals
+	/// the same garbage
+	pub fn new(seed: u64) -> Self {
+		Self {
+			rng: Rng::with_seed(seed).fork(),
+			hole: None,
+			pending: 0,
+			next_at: Duration::ZERO,
+		}
+	}
+
+	/// A full row with one hole, the messiness decides how likely the hole
+	/// moves away from the column of the previous row
+	pub fn next_row(&mut self, cols: usize) -> Vec<Option<TetrominoKind>> {
+		let messiness = global_setting().dig_messiness();
+
+		let hole = match self.hole {
+			Some(hole) if hole < cols => {
+				if cols > 1 && self.rng.u8(0..100) < messiness {
+					let other = self.rng.usize(0..cols - 1);
+					if other >= hole {
+						other + 1
+					} else {
+						other
+					}
+				} else {
+					hole
+				}
+			}
+			_ => self.rng.usize(0..cols),
+		};
+
+		self.hole = Some(hole);
+
+		let mut row = vec![Some(TetrominoKind::Garbage); cols];
+		row[hole] = None;
+
+		row
+	}
+
+	/// Queue rising rows by the game time
+	pub fn update(&mut self, elapsed: Duration) {
+		let interval = global_setting().garbage_interval();
+
+		if interval == 0 {
+			self.next_at = Duration::ZERO;
+			return;
+		}
+
+		let interval = Duration::from_secs(interval as u64);
+
+		if self.next_at.is_zero() {
+			self.next_at = elapsed + interval;
+		}
+
+		while elapsed >= self.next_at {
+			self.pending += 1;
+			self.next_at += interval;
+		}
+	}
+
+	pub fn take_pending(&mut self) -> usize {
+		std::mem::take(&mut self.pending)
+	}
+}
+
+impl Default for Garbage {
+	fn default() -> Self {
+		Self::new(fastrand::u64(..))
+	}
+}
@@ -0,0 +1,74 @@
+use std::collections::VecDeque;
+
+use super::{
+	bag::Bag, hold_board::HoldBoard, main_board::MainBoard,
+	next_board::NextBoard, stats::Stats, tetromino::Tetromino,
+};
+use crate::common::Reset;
+
+/// Everything a placement changes, taken right before a piece locks with
+/// the piece back at its spawn position
+#[derive(Clone)]
+pub struct Snapshot {
+	pub board: MainBoard,
+	pub bag: Bag,
+	pub next_board: NextBoard,
+	pub hold_board: HoldBoard,
+	pub stats: Stats,
+	pub alive_tetromino: Tetromino,
+}
+
+/// Placements of the practice mode that can be undone and redone
+#[derive(Default)]
+pub struct History {
+	undo: VecDeque<Snapshot>,
+	redo: Vec<Snapshot>,
+}
+
+impl History {
+	/// A new placement drops the placements that were undone
+	pub fn push(&mut self, snapshot: Snapshot) {
+		if self.undo.len() == HISTORY_MAX_LEN {
+			self.undo.pop_front();
+		}
+		self.undo.push_back(snapshot);
+		self.redo.clear();
+	}
+
+	/// Returns the snapshot to go back to, `current` is kept for redoing
+	pub fn undo(&mut self, current: Snapshot) -> Option<Snapshot> {
+		let snapshot = self.undo.pop_back()?;
+		self.redo.push(current);
+
+		Some(snapshot)
+	}
+
+	/// Returns the snapshot to go forward to, `current` is kept for undoing
+	pub fn redo(&mut self, current: Snapshot) -> Option<Snapshot> {
+		let snapshot = self.redo.pop()?;
+		self.undo.push_back(current);
+
+		Some(snapshot)
+	}
+
+	/// Returns the snapshot of the last placement, which is gone for good
+	pub fn drop_last(&mut self) -> Option<Snapshot> {
+		self.undo.pop_back()
+	}
+
+	pub fn undo_len(&self) -> usize {
+		self.undo.len()
+	}
+
+	pub fn redo_len(&self) -> usize {
+		self.redo.len()
+	}
+}
+
+impl Reset for History {
+	fn reset(&mut self) {
+		*self = Self::default();
+	}
+}
+
+const HISTORY_MAX_LEN: usize = 1000;
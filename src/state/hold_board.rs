@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	common::{Board, Reset, TetrominoKind},
+	consts::{HOLD_BOARD_COLS, HOLD_BOARD_ROWS},
+};
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct HoldBoard {
+	cells: Vec<Vec<Option<TetrominoKind>>>,
+	pub current: Option<TetrominoKind>,
+	/// Only one hold is allowed per piece, cleared when the piece locks
+	pub used: bool,
+}
+
+impl HoldBoard {
+	pub fn new() -> Self {
+		Self {
+			cells: vec![vec![None; HOLD_BOARD_COLS]; HOLD_BOARD_ROWS],
+			current: None,
+			used: false,
+		}
+	}
+
+	pub fn set_hold(&mut self, kind: TetrominoKind) {
+		self.current = Some(kind);
+		self.used = true;
+
+		for line in &mut self.cells {
+			for cell in line {
+				if cell.is_some() {
+					*cell = None;
+				}
+			}
+		}
+
+		for p in kind.init_position(0).to_usize_points() {
+			self.cells[p.y][p.x] = Some(kind);
+		}
+	}
+}
+
+impl Board for HoldBoard {
+	fn get_kind(&self, x: usize, y: usize) -> Option<&TetrominoKind> {
+		self.cells[y][x].as_ref()
+	}
+}
+
+impl Reset for HoldBoard {
+	fn reset(&mut self) {
+		*self = Self::new();
+	}
+}
@@ -1,38 +1,58 @@
-use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+use std::{cell::RefCell, collections::VecDeque, rc::Rc, time::Duration};
 
 use serde::{Deserialize, Serialize};
 
 use super::{particles::Particles, Tetromino};
 use crate::{
 	common::{Board, Position, Reset, TetrominoKind},
-	consts::{MAIN_BOARD_COLS, MAIN_BOARD_ROWS},
-	global::{global_audio, Sound},
+	consts::MAIN_BOARD_BUFFER_ROWS,
+	global::{board_cols, board_rows, global_audio, Sound},
 };
 
 pub type SharedMainBoard = Rc<RefCell<MainBoard>>;
 
+/// The size of a saved board comes from its cells
 #[derive(Clone, Deserialize, Serialize)]
 pub struct MainBoard {
 	cells: VecDeque<Vec<Option<TetrominoKind>>>,
-	#[serde(skip)]
+	/// Rows of the initial garbage still on the board, they sit right above
+	/// the rising garbage
+	#[serde(default)]
+	initial_garbage: usize,
+	/// Garbage rows risen during the game, always at the bottom
+	#[serde(default)]
+	rising_garbage: usize,
+	/// Game time in milliseconds when every cell was locked, cells that were
+	/// never locked by a piece count as locked at the start
+	#[serde(default)]
+	lock_times: VecDeque<Vec<u32>>,
+	#[serde(default)]
 	pub line_clear: LineClear,
 	#[serde(skip)]
 	pub particles: Particles,
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Deserialize, Serialize)]
 pub struct LineClear {
 	pub in_progress: bool,
 	lines: Vec<usize>,
 	curosr: usize,
+	/// Game time when the lines were locked
+	started: Duration,
 }
 
 impl MainBoard {
 	fn new() -> Self {
 		Self {
 			cells: VecDeque::from_iter(vec![
-				vec![None; MAIN_BOARD_COLS];
-				MAIN_BOARD_ROWS
+				vec![None; board_cols()];
+				board_rows()
+			]),
+			initial_garbage: 0,
+			rising_garbage: 0,
+			lock_times: VecDeque::from_iter(vec![
+				vec![0; board_cols()];
+				board_rows()
 			]),
 			line_clear: LineClear::default(),
 			particles: Particles::default(),
@@ -43,9 +63,65 @@ impl MainBoard {
 		Rc::new(RefCell::new(Self::new()))
 	}
 
-	pub fn lock_tetromino(&mut self, tetromino: &Tetromino) -> usize {
+	pub fn cols(&self) -> usize {
+		self.cells.front().map_or(0, |line| line.len())
+	}
+
+	pub fn visible_rows(&self) -> usize {
+		self.cells.len().saturating_sub(MAIN_BOARD_BUFFER_ROWS)
+	}
+
+	/// Fill the bottom rows with a puzzle layout
+	pub fn set_layout(&mut self, layout: &[Vec<Option<TetrominoKind>>]) {
+		let start = self.cells.len().saturating_sub(layout.len());
+
+		for (line, row) in self.cells.iter_mut().skip(start).zip(layout) {
+			line.clone_from(row);
+		}
+	}
+
+	pub fn initial_garbage(&self) -> usize {
+		self.initial_garbage
+	}
+
+	/// Push a row in at the bottom, returns false when filled cells are
+	/// pushed out of the top
+	pub fn add_garbage(
+		&mut self,
+		row: Vec<Option<TetrominoKind>>,
+		initial: bool,
+	) -> bool {
+		self.fix_lock_times();
+
+		let fits = self
+			.cells
+			.pop_front()
+			.map_or(true, |line| line.iter().all(|cell| cell.is_none()));
+
+		self.lock_times.pop_front();
+		self.lock_times.push_back(vec![0; row.len()]);
+		self.cells.push_back(row);
+
+		if initial {
+			self.initial_garbage += 1;
+		} else {
+			self.rising_garbage += 1;
+		}
+
+		fits
+	}
+
+	/// `time` is the game time, see [`MainBoard::lock_time`]
+	pub fn lock_tetromino(
+		&mut self,
+		tetromino: &Tetromino,
+		time: Duration,
+	) -> usize {
+		self.fix_lock_times();
+
 		for p in tetromino.position.to_usize_points() {
 			self.cells[p.y][p.x] = Some(tetromino.kind);
+			self.lock_times[p.y][p.x] = time.as_millis() as u32;
 		}
 
 		for (i, line) in self.cells.iter().enumerate() {
@@ -55,11 +131,14 @@ impl MainBoard {
 			self.line_clear.lines.push(i);
 		}
 
+		self.count_cleared_garbage();
+
 		global_audio(|audio| audio.play_sound(Sound::Lock));
 
 		let num = self.line_clear.lines.len();
 		if num != 0 {
 			self.line_clear.in_progress = true;
+			self.line_clear.started = time;
 			global_audio(|audio| audio.play_sound(Sound::Clear));
 		}
 
@@ -73,6 +152,63 @@ impl MainBoard {
 			.any(|p| self.cells[p.y][p.x].is_some())
 	}
 
+	pub fn is_empty(&self) -> bool {
+		self.cells
+			.iter()
+			.all(|line| line.iter().all(|cell| cell.is_none()))
+	}
+
+	/// Whether the cell is filled or outside the board
+	pub fn is_blocked(&self, x: i8, y: i8) -> bool {
+		if x < 0 || y < 0 {
+			return true;
+		}
+
+		self.cells
+			.get(y as usize)
+			.and_then(|line| line.get(x as usize))
+			.map_or(true, |cell| cell.is_some())
+	}
+
+	/// Game time when the cell was locked
+	pub fn lock_time(&self, x: usize, y: usize) -> Duration {
+		let millis = self
+			.lock_times
+			.get(y)
+			.and_then(|line| line.get(x))
+			.copied()
+			.unwrap_or(0);
+
+		Duration::from_millis(millis as u64)
+	}
+
+	/// Saves from before the lock times were kept have none
+	fn fix_lock_times(&mut self) {
+		if self.lock_times.len() != self.cells.len() {
+			self.lock_times = VecDeque::from_iter(vec![
+				vec![0; self.cols()];
+				self.cells.len()
+			]);
+		}
+	}
+
+	fn count_cleared_garbage(&mut self) {
+		let rows = self.cells.len();
+		let (mut rising, mut initial) = (0, 0);
+
+		for line in &self.line_clear.lines {
+			let from_bottom = rows - 1 - line;
+			if from_bottom < self.rising_garbage {
+				rising += 1;
+			} else if from_bottom < self.rising_garbage + self.initial_garbage {
+				initial += 1;
+			}
+		}
+
+		self.rising_garbage -= rising;
+		self.initial_garbage -= initial;
+	}
+
 	fn clear_cell(&mut self) {
 		for line in &self.line_clear.lines {
 			self.cells[*line][self.line_clear.curosr] = None;
@@ -80,23 +216,41 @@ impl MainBoard {
 		}
 	}
 
-	pub fn update_line_clear(&mut self) -> bool {
-		self.clear_cell();
-		self.line_clear.curosr += 1;
+	/// The cells are emptied column by column over `delay`, `now` is the
+	/// game time, returns true once the lines are removed
+	pub fn update_line_clear(
+		&mut self,
+		now: Duration,
+		delay: Duration,
+	) -> bool {
+		let elapsed = now.saturating_sub(self.line_clear.started);
+		let cols = self.cols();
+		let cursor = if elapsed >= delay {
+			cols
+		} else {
+			(cols as u128 * elapsed.as_millis() / delay.as_millis()) as usize
+		};
 
-		if self.line_clear.curosr >= MAIN_BOARD_COLS {
-			self.line_clear.curosr = 0;
-			self.line_clear.in_progress = false;
-			for line in &self.line_clear.lines {
-				self.cells.remove(*line);
-				self.cells.push_front(vec![None; MAIN_BOARD_COLS]);
-			}
-			self.line_clear.lines.clear();
+		while self.line_clear.curosr < cursor {
+			self.clear_cell();
+			self.line_clear.curosr += 1;
+		}
 
-			true
-		} else {
-			false
+		if elapsed < delay {
+			return false;
+		}
+
+		self.line_clear.curosr = 0;
+		self.line_clear.in_progress = false;
+		for line in &self.line_clear.lines {
+			self.cells.remove(*line);
+			self.cells.push_front(vec![None; self.cols()]);
+			self.lock_times.remove(*line);
+			self.lock_times.push_front(vec![0; self.cols()]);
 		}
+		self.line_clear.lines.clear();
+
+		true
 	}
 }
 
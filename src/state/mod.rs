@@ -1,37 +1,68 @@
 pub mod bag;
+pub mod editor;
 pub mod focus;
+pub mod game_mode;
 pub mod game_over_menu;
+pub mod garbage;
 pub mod ghost_tetromino;
+pub mod history;
+pub mod hold_board;
 pub mod main_board;
+pub mod mode_menu;
+pub mod modifier_menu;
+pub mod modifiers;
 pub mod next_board;
 pub mod particles;
 pub mod pause_menu;
+pub mod puzzle;
+pub mod puzzle_menu;
+pub mod randomizer;
 pub mod scores;
 pub mod setting_menu;
 pub mod start_menu;
 pub mod stats;
+pub mod stopwatch;
 pub mod tetromino;
 
+use std::{collections::BTreeMap, time::Duration};
+
 use bag::Bag;
+use editor::Editor;
 use focus::{Focus, Scene};
-use game_over_menu::{game_over_menu_idx, GameOverMenu};
+use game_mode::GameMode;
+use game_over_menu::{game_over_menu_idx, GameOverMenu, TopOut};
+use garbage::Garbage;
 use ghost_tetromino::GhostTetromino;
+use history::{History, Snapshot};
+use hold_board::HoldBoard;
 use main_board::{MainBoard, SharedMainBoard};
+use mode_menu::{mode_menu_idx, ModeMenu};
+use modifier_menu::ModifierMenu;
+use modifiers::Modifiers;
 use next_board::NextBoard;
 use pause_menu::{pause_menu_idx, PauseMenu};
-use scores::Scores;
+use puzzle::{Puzzle, Setup};
+use puzzle_menu::PuzzleMenu;
+use scores::{
+	leaderboard_tag, DigRecord, Leaderboards, ScoreRecord, ScoresPage,
+};
 use setting_menu::SettingMenu;
 use start_menu::{start_menu_idx, StartMenu};
 use stats::Stats;
+use stopwatch::Stopwatch;
 use tetromino::{Tetromino, TetrominoAction};
 
 use crate::{
-	common::{Menu, Reset},
-	consts::MAIN_BOARD_ROWS,
-	global::{global_audio, is_locked, set_played, Sound},
+	common::{Menu, Reset, TetrominoKind},
+	global::{
+		board_rows, global_audio, global_setting, is_key_release_supported,
+		is_locked, set_board_size, set_played, setting::SONIC_DROP, Sound,
+	},
 	handler::{Event, SubHandler},
 };
 
+const STACK_REVEAL: Duration = Duration::from_millis(1000);
+
 pub struct State {
 	pub handler: SubHandler,
 	pub running: bool,
@@ -40,14 +71,45 @@ pub struct State {
 	pub pause_menu: PauseMenu,
 	pub game_over_menu: GameOverMenu,
 	pub setting_menu: SettingMenu,
+	pub mode_menu: ModeMenu,
+	pub puzzle_menu: PuzzleMenu,
+	pub modifier_menu: ModifierMenu,
+	pub editor: Editor,
+	pub mode: GameMode,
+	/// Modifiers of the current game
+	pub modifiers: Modifiers,
+	/// The puzzle being played in the puzzle mode
+	pub puzzle: Option<Puzzle>,
+	/// Board from the board editor the current game started from
+	pub custom_setup: Option<Setup>,
+	pub stopwatch: Stopwatch,
+	/// Game time until which a fading or invisible stack is shown
+	pub stack_revealed_until: Duration,
+	/// Clock time when the next piece enters, see [`Stopwatch::clock`]
+	pub spawn_at: Option<Duration>,
+	/// Rotation pressed between pieces, the next piece enters with it
+	initial_rotation: Option<TetrominoAction>,
+	/// Hold pressed between pieces, the next piece goes to the hold as it
+	/// enters
+	initial_hold: bool,
+	pub garbage: Garbage,
+	/// Placements of the practice mode
+	pub history: History,
 	pub bag: Bag,
 	pub board: SharedMainBoard,
 	pub next_board: NextBoard,
+	pub hold_board: HoldBoard,
 	pub alive_tetromino: Tetromino,
 	pub ghost_tetromino: GhostTetromino,
 	pub count_down: u8,
 	pub stats: Stats,
-	pub scores: Scores,
+	pub leaderboards: Leaderboards,
+	/// Leaderboards of games with modifiers or another scoring, by the
+	/// leaderboard tag
+	pub modified_leaderboards: BTreeMap<String, Leaderboards>,
+	pub scores_page: ScoresPage,
+	/// Seed from the command line, every new game starts with it
+	pub seed: Option<u64>,
 }
 
 impl State {
@@ -63,29 +125,49 @@ impl State {
 			pause_menu: PauseMenu::new(),
 			game_over_menu: GameOverMenu::new(),
 			setting_menu: SettingMenu::new(),
+			mode_menu: ModeMenu::new(),
+			puzzle_menu: PuzzleMenu::new(),
+			modifier_menu: ModifierMenu::new(),
+			editor: Editor::new(),
+			mode: GameMode::default(),
+			modifiers: Modifiers::default(),
+			puzzle: None,
+			custom_setup: None,
+			stopwatch: Stopwatch::default(),
+			stack_revealed_until: Duration::ZERO,
+			spawn_at: None,
+			initial_rotation: None,
+			initial_hold: false,
+			garbage: Garbage::default(),
+			history: History::default(),
 			bag: Bag::new(),
 			board,
 			next_board: NextBoard::new(),
+			hold_board: HoldBoard::new(),
 			alive_tetromino,
 			ghost_tetromino: GhostTetromino::default(),
 			count_down: 0,
 			stats: Stats::new(),
-			scores: Scores::new(),
+			leaderboards: Leaderboards::default(),
+			modified_leaderboards: BTreeMap::new(),
+			scores_page: ScoresPage::default(),
+			seed: None,
 		}
 	}
 
 	pub fn handle_event(&mut self, event: Event) {
-		if self.board.borrow().line_clear.in_progress {
-			return;
-		}
-
 		match self.focus.current() {
 			Scene::StartMenu => self.handle_start_menu(event),
 			Scene::Game => self.handle_game_play(event),
 			Scene::PauseMenu => self.handle_pause_menu(event),
 			Scene::SettingMenu => self.handle_setting_menu(event),
 			Scene::GameOverMenu => self.handle_game_over_menu(event),
-			Scene::Scores | Scene::Help | Scene::About => {
+			Scene::ModeMenu => self.handle_mode_menu(event),
+			Scene::PuzzleMenu => self.handle_puzzle_menu(event),
+			Scene::ModifierMenu => self.handle_modifier_menu(event),
+			Scene::Editor => self.handle_editor(event),
+			Scene::Scores => self.handle_scores(event),
+			Scene::Help | Scene::About => {
 				if event == Event::Esc {
 					self.focus.back();
 				}
@@ -119,27 +201,255 @@ impl State {
 		if self.count_down > 0 {
 			self.focus.to(Scene::Game);
 			self.update_ghost_tetromino();
-			self.handler.pause();
+			self.pause();
 			self.handler.start_count_down(self.count_down);
 			global_audio(|audio| audio.play_music());
+			set_played(true);
 		} else {
-			self.new_game();
+			self.focus.push(Scene::ModeMenu);
 		}
+	}
+
+	/// A fading or invisible stack shows up for a moment after line clears
+	/// and for good once the game is over
+	pub fn is_stack_revealed(&self) -> bool {
+		self.focus.contains(Scene::GameOverMenu)
+			|| self.stopwatch.elapsed() < self.stack_revealed_until
+	}
+
+	/// Whether there is a game to resume or to save
+	pub fn has_game(&self) -> bool {
+		self.count_down > 0
+			|| (self.focus.contains(Scene::Game)
+				&& !self.focus.contains(Scene::GameOverMenu))
+	}
+
+	fn handle_mode_menu(&mut self, event: Event) {
+		match event {
+			Event::Up => self.mode_menu.up(),
+			Event::Down => self.mode_menu.down(),
+			Event::Left => self.mode_menu.handle_change(false),
+			Event::Right => self.mode_menu.handle_change(true),
+			Event::Enter => {
+				match self.mode_menu.cursor() {
+					mode_menu_idx::PUZZLE => {
+						self.focus.push(Scene::PuzzleMenu);
+						return;
+					}
+					mode_menu_idx::EDITOR => {
+						self.editor.open();
+						self.focus.push(Scene::Editor);
+						return;
+					}
+					mode_menu_idx::MODIFIERS => {
+						self.focus.push(Scene::ModifierMenu);
+						return;
+					}
+					_ => (),
+				}
+				self.mode = self.mode_menu.mode();
+				self.puzzle = None;
+				self.custom_setup = None;
+				self.new_game();
+				set_played(true);
+			}
+			Event::Esc => self.focus.back(),
+			_ => (),
+		}
+	}
+
+	fn handle_puzzle_menu(&mut self, event: Event) {
+		match event {
+			Event::Up => self.puzzle_menu.up(),
+			Event::Down => self.puzzle_menu.down(),
+			Event::Enter => {
+				let Some(puzzle) = self.puzzle_menu.current() else {
+					return;
+				};
+				self.mode = GameMode::Puzzle {
+					objective: puzzle.objective,
+				};
+				self.puzzle = Some(puzzle.clone());
+				self.custom_setup = None;
+				self.new_game();
+				set_played(true);
+			}
+			Event::Esc => self.focus.back(),
+			_ => (),
+		}
+	}
+
+	fn handle_modifier_menu(&mut self, event: Event) {
+		match event {
+			Event::Up => self.modifier_menu.up(),
+			Event::Down => self.modifier_menu.down(),
+			Event::Left => self.modifier_menu.handle_change(false),
+			Event::Right | Event::Enter => {
+				self.modifier_menu.handle_change(true)
+			}
+			Event::Esc => {
+				self.focus.back();
+				self.modifier_menu.reset();
+			}
+			_ => (),
+		}
+	}
+
+	fn handle_editor(&mut self, event: Event) {
+		match event {
+			Event::Up => self.editor.move_cursor(0, -1),
+			Event::Down => self.editor.move_cursor(0, 1),
+			Event::Left => self.editor.move_cursor(-1, 0),
+			Event::Right => self.editor.move_cursor(1, 0),
+			Event::LeftToWall => self.editor.cursor_to_wall(false),
+			Event::RightToWall => self.editor.cursor_to_wall(true),
+			Event::Space => self.editor.paint(),
+			Event::C => self.editor.change_brush(true),
+			Event::Z => self.editor.change_brush(false),
+			Event::A => self.editor.add_piece(),
+			Event::U => self.editor.remove_piece(),
+			Event::S => self.editor.save(),
+			Event::O => self.editor.load_next(),
+			Event::Enter => {
+				// Played in the practice mode, so a setup can be retried by
+				// undoing
+				match self.editor.setup() {
+					Ok(setup) => {
+						self.mode = GameMode::Practice {
+							gravity: global_setting().practice_gravity(),
+						};
+						self.puzzle = None;
+						self.custom_setup = Some(setup);
+						self.new_game();
+						set_played(true);
+					}
+					Err(e) => {
+						self.editor.message = Some(e.to_string().to_uppercase())
+					}
+				}
+			}
+			Event::Esc => self.focus.back(),
+			_ => (),
+		}
+	}
+
+	/// Leaderboards of the games with this leaderboard tag, `None` when no
+	/// such game has been recorded yet
+	pub fn leaderboards(&self, tag: &str) -> Option<&Leaderboards> {
+		if tag.is_empty() {
+			Some(&self.leaderboards)
+		} else {
+			self.modified_leaderboards.get(tag)
+		}
+	}
+
+	/// Leaderboards the current game is recorded in
+	fn leaderboards_mut(&mut self) -> &mut Leaderboards {
+		let tag = leaderboard_tag(&self.modifiers, self.stats.scoring);
+
+		if tag.is_empty() {
+			&mut self.leaderboards
+		} else {
+			self.modified_leaderboards.entry(tag).or_default()
+		}
+	}
+
+	fn handle_scores(&mut self, event: Event) {
+		match event {
+			Event::Left => self.scores_page.change(false),
+			Event::Right => self.scores_page.change(true),
+			Event::Esc => self.focus.back(),
+			_ => (),
+		}
+	}
+
+	/// Back to the start menu, the current game is dropped
+	fn main_menu(&mut self) {
+		self.handler.cancel_lock();
+		self.handler.cancel_grvity();
+		self.count_down = 0;
+		self.focus.to(Scene::StartMenu);
+		global_audio(|audio| audio.stop_music());
+	}
+
+	fn pause(&mut self) {
+		self.stopwatch.update();
+		self.handler.pause();
+	}
 
-		set_played(true);
+	fn resume(&mut self) {
+		self.stopwatch.update();
+		self.handler.cancel_pause();
 	}
 
 	fn new_game(&mut self) {
 		self.focus.to(Scene::Game);
+		self.modifiers = Modifiers::from_setting();
+		let setup = self
+			.puzzle
+			.as_ref()
+			.map(|puzzle| &puzzle.setup)
+			.or(self.custom_setup.as_ref())
+			.cloned();
+		let (cols, rows) = match &setup {
+			Some(setup) => {
+				// A setup is always played on the board of its layout
+				self.modifiers.big = false;
+				setup.board_size()
+			}
+			None => {
+				self.modifiers.board_size(
+					global_setting().board_cols(),
+					global_setting().board_visible_rows(),
+				)
+			}
+		};
+		set_board_size(cols, rows);
 		self.board.borrow_mut().reset();
 		self.next_board.reset();
-		self.bag.reset();
+		self.hold_board.reset();
+		match self.seed {
+			Some(seed) => self.bag.reseed(seed),
+			None => self.bag.reset(),
+		}
+		if let Some(setup) = setup {
+			self.board.borrow_mut().set_layout(&setup.cells);
+			if !setup.pieces.is_empty() {
+				// A puzzle is over once its pieces run out
+				self.bag.deal_sequence(setup.pieces, self.puzzle.is_some());
+			}
+		}
 		self.stats.reset();
-		self.alive_tetromino.set_next(self.bag.next());
-		self.next_board.set_next(self.bag.next());
+		self.stats.scoring = global_setting().scoring();
+		if self.mode.has_start_level() {
+			self.stats.set_start_level(global_setting().start_level());
+		}
+		self.stopwatch.reset();
+		self.stack_revealed_until = Duration::ZERO;
+		self.spawn_at = None;
+		self.initial_rotation = None;
+		self.initial_hold = false;
+		self.history.reset();
+		self.garbage = Garbage::new(self.bag.seed());
+		if let GameMode::Dig {
+			rows,
+		} = self.mode
+		{
+			let mut board = self.board.borrow_mut();
+			for _ in 0..rows {
+				let row = self.garbage.next_row(board.cols());
+				board.add_garbage(row, true);
+			}
+			self.stats.garbage_left = rows;
+		}
+		self.alive_tetromino.set_next(
+			self.next_board
+				.next(&mut self.bag)
+				.expect("a new game should have pieces"),
+		);
 		self.update_ghost_tetromino();
-		self.handler.spawn_gravity();
-		self.handler.cancel_pause();
+		self.handler.spawn_gravity(self.stats.level);
+		self.resume();
 
 		global_audio(|audio| audio.play_music());
 	}
@@ -150,9 +460,11 @@ impl State {
 		if event == Event::CountDown {
 			self.count_down -= 1;
 			if self.count_down == 0 {
-				self.handler.cancel_pause();
-				self.handler.spawn_gravity();
-				self.check_lock();
+				self.resume();
+				self.handler.spawn_gravity(self.stats.level);
+				if !self.is_between_pieces() {
+					self.check_lock();
+				}
 			}
 			global_audio(|audio| audio.play_sound(Sound::Menu));
 			return;
@@ -162,6 +474,21 @@ impl State {
 			return;
 		}
 
+		let event = if self.modifiers.mirror {
+			event.mirrored()
+		} else {
+			event
+		};
+
+		if self.is_between_pieces()
+			&& !matches!(
+				event,
+				Event::Esc | Event::P | Event::FocusLost | Event::DownRelease
+			) {
+			self.buffer_initial_action(event);
+			return;
+		}
+
 		let mut changed = false;
 
 		match &event {
@@ -171,19 +498,36 @@ impl State {
 			Event::Right => {
 				changed = self.alive_tetromino.walk(WalkRight);
 			}
-			Event::Down | Event::Gravity => {
-				changed = self.alive_tetromino.walk(SoftDrop);
-				if changed {
-					self.stats.score += 1;
+			Event::LeftToWall => {
+				while self.alive_tetromino.walk(WalkLeft) {
+					changed = true;
+				}
+			}
+			Event::RightToWall => {
+				while self.alive_tetromino.walk(WalkRight) {
+					changed = true;
 				}
 			}
+			Event::Down => {
+				changed = self.soft_drop(1);
+				if is_key_release_supported() {
+					self.handler.start_soft_drop();
+				}
+			}
+			Event::DownRelease => {
+				self.handler.stop_soft_drop();
+			}
+			Event::SoftDrop(rows) => {
+				changed = self.soft_drop(*rows);
+			}
+			Event::Gravity(rows) if self.mode.has_gravity() => {
+				changed = self.alive_tetromino.fall(*rows) > 0;
+			}
 			Event::Space => {
 				let y1 = self.ghost_tetromino.position.bottom_point().y;
 				let y2 = self.alive_tetromino.position.bottom_point().y;
-				self.alive_tetromino
-					.position
-					.clone_from(&self.ghost_tetromino.position);
-				self.stats.score += (y1 - y2) as u32 * 2;
+				self.alive_tetromino.drop_to(&self.ghost_tetromino.position);
+				self.stats.hard_drop((y1 - y2) as u32);
 				self.handler.cancel_lock();
 				self.lock_tetromino();
 			}
@@ -193,8 +537,21 @@ impl State {
 			Event::Z => {
 				changed = self.alive_tetromino.rotate(RotateLeft);
 			}
+			Event::A => {
+				changed = self.alive_tetromino.rotate(Rotate180);
+			}
+			Event::C => {
+				self.hold();
+			}
+			Event::U => {
+				self.undo();
+			}
+			Event::R => {
+				self.redo();
+			}
 			Event::Esc | Event::P | Event::FocusLost => {
-				self.handler.pause();
+				self.handler.stop_soft_drop();
+				self.pause();
 				self.focus.push(Scene::PauseMenu);
 			}
 			Event::LockEnd => {
@@ -206,30 +563,55 @@ impl State {
 			_ => (),
 		};
 
-		let is_move_event =
-			matches!(event, Event::Left | Event::Right | Event::Up | Event::Z);
+		let is_move_event = matches!(
+			event,
+			Event::Left | Event::Right | Event::Up | Event::Z | Event::A
+		);
+		let is_shift_to_wall =
+			matches!(event, Event::LeftToWall | Event::RightToWall);
 
 		if changed {
-			if is_move_event {
+			if is_move_event || is_shift_to_wall {
 				self.update_ghost_tetromino();
 			}
 			self.check_lock();
 		}
 
-		if is_move_event || event == Event::Down {
+		if is_move_event
+			|| event == Event::Down
+			|| (is_shift_to_wall && changed)
+		{
 			global_audio(|audio| audio.play_sound(Sound::Move));
 		}
 	}
 
+	fn soft_drop(&mut self, rows: u8) -> bool {
+		if global_setting().soft_drop() == SONIC_DROP {
+			let y1 = self.ghost_tetromino.position.bottom_point().y;
+			let y2 = self.alive_tetromino.position.bottom_point().y;
+			self.alive_tetromino.drop_to(&self.ghost_tetromino.position);
+			self.stats.soft_drop((y1 - y2) as u32);
+			y1 != y2
+		} else {
+			let fallen = self.alive_tetromino.fall(rows);
+			self.stats.soft_drop(fallen as u32);
+			fallen > 0
+		}
+	}
+
 	fn check_lock(&mut self) {
 		let fit_together =
 			self.alive_tetromino.position == self.ghost_tetromino.position;
+		if self.alive_tetromino.reach_lowest_row() {
+			self.handler.reset_lock();
+		} else if is_locked() {
+			self.handler.refresh_lock();
+		}
+
 		if is_locked() {
 			if !fit_together {
 				self.alive_tetromino.blink = false;
 				self.handler.cancel_lock();
-			} else {
-				self.handler.refresh_lock();
 			}
 		} else if fit_together {
 			self.handler.start_lock();
@@ -238,7 +620,7 @@ impl State {
 
 	fn update_ghost_tetromino(&mut self) {
 		let bottom_point = self.alive_tetromino.position.bottom_point();
-		let mut max_distance = MAIN_BOARD_ROWS as i8 - bottom_point.y - 1;
+		let mut max_distance = board_rows() as i8 - bottom_point.y - 1;
 		let mut virtual_tetromino = self.alive_tetromino.clone();
 
 		while max_distance > 0 {
@@ -260,41 +642,301 @@ impl State {
 	}
 
 	fn lock_tetromino(&mut self) {
-		if self.alive_tetromino.position.is_outside_the_visible() {
-			self.game_over();
+		// Taken before the lock out, a practice game goes back to it
+		if let GameMode::Practice {
+			..
+		} = self.mode
+		{
+			self.history.push(self.snapshot());
+		}
+
+		// Locking partly above the visible rows is fine
+		if self.alive_tetromino.position.is_above_the_visible() {
+			self.game_over(TopOut::Lock);
 			return;
 		}
 
+		let t_spin = self.alive_tetromino.t_spin();
+
+		self.stopwatch.update();
+
 		let cleared_lines = self
 			.board
 			.borrow_mut()
-			.lock_tetromino(&self.alive_tetromino);
+			.lock_tetromino(&self.alive_tetromino, self.stopwatch.elapsed());
+
+		if cleared_lines != 0 {
+			self.stack_revealed_until = self.stopwatch.elapsed() + STACK_REVEAL;
+		}
 
 		self.alive_tetromino.hidden();
 		self.ghost_tetromino.hidden();
+		self.hold_board.used = false;
 
 		let previous_level = self.stats.level;
 
-		self.stats.update(cleared_lines);
+		self.stats.update(cleared_lines, t_spin);
+		self.stats.garbage_left = self.board.borrow().initial_garbage() as u32;
 
 		if self.stats.level > previous_level {
 			self.handler.change_level(self.stats.level);
 		}
 
+		// The time is taken when the last line is locked, the game ends once
+		// the line clear is done
+		if self.mode.is_finished(&self.stats, &self.stopwatch) {
+			self.stopwatch.stop();
+		}
+
 		if cleared_lines != 0 {
+			// Without a line clear delay the lines are removed right away
+			self.update_line_clear();
+			return;
+		}
+
+		if self.mode.is_finished(&self.stats, &self.stopwatch) {
+			self.end_game(true);
+			return;
+		}
+
+		self.prepare_next_tetromino();
+	}
+
+	/// Rising garbage comes in first, then the next piece enters once the
+	/// entry delay is over
+	fn prepare_next_tetromino(&mut self) {
+		if !self.rise_garbage() {
+			return;
+		}
+
+		let delay = global_setting().entry_delay();
+
+		if delay == 0 {
+			self.next_alive_tetromino();
+		} else {
+			self.spawn_at = Some(
+				self.stopwatch.clock() + Duration::from_millis(delay as u64),
+			);
+		}
+	}
+
+	/// Nothing is under control until the next piece enters, but rotations
+	/// and holds are kept for it, known as IRS and IHS
+	fn buffer_initial_action(&mut self, event: Event) {
+		use TetrominoAction::*;
+
+		match event {
+			Event::Up => self.initial_rotation = Some(RotateRight),
+			Event::Z => self.initial_rotation = Some(RotateLeft),
+			Event::A => self.initial_rotation = Some(Rotate180),
+			Event::C => self.initial_hold = true,
+			_ => (),
+		}
+	}
+
+	/// During the line clear and the entry delay
+	fn is_between_pieces(&self) -> bool {
+		self.spawn_at.is_some() || self.board.borrow().line_clear.in_progress
+	}
+
+	/// The current placement with the alive piece back at its spawn position
+	fn snapshot(&self) -> Snapshot {
+		let mut alive_tetromino = self.alive_tetromino.clone();
+		alive_tetromino.set_next(alive_tetromino.kind);
+
+		Snapshot {
+			board: self.board.borrow().clone(),
+			bag: self.bag.clone(),
+			next_board: self.next_board.clone(),
+			hold_board: self.hold_board.clone(),
+			stats: self.stats.clone(),
+			alive_tetromino,
+		}
+	}
+
+	fn restore(&mut self, snapshot: Snapshot) {
+		self.handler.cancel_lock();
+		self.board.replace(snapshot.board);
+		self.bag = snapshot.bag;
+		self.next_board = snapshot.next_board;
+		self.hold_board = snapshot.hold_board;
+		self.stats = snapshot.stats;
+		self.alive_tetromino = snapshot.alive_tetromino;
+		self.alive_tetromino.set_board(self.board.clone());
+		self.handler.change_level(self.stats.level);
+		self.update_ghost_tetromino();
+		self.check_lock();
+
+		global_audio(|audio| audio.play_sound(Sound::Move));
+	}
+
+	/// Back to before the last placement of the practice mode
+	fn undo(&mut self) {
+		if !matches!(self.mode, GameMode::Practice { .. })
+			|| self.history.undo_len() == 0
+		{
 			return;
 		}
 
-		self.next_alive_tetromino();
+		let current = self.snapshot();
+
+		if let Some(snapshot) = self.history.undo(current) {
+			self.restore(snapshot);
+		}
 	}
 
-	fn game_over(&mut self) {
+	fn redo(&mut self) {
+		if !matches!(self.mode, GameMode::Practice { .. })
+			|| self.history.redo_len() == 0
+		{
+			return;
+		}
+
+		let current = self.snapshot();
+
+		if let Some(snapshot) = self.history.redo(current) {
+			self.restore(snapshot);
+		}
+	}
+
+	/// Rising garbage comes in between locking a piece and spawning the next
+	/// one, returns false when it tops out
+	fn rise_garbage(&mut self) -> bool {
+		let rows = self.garbage.take_pending();
+		let mut fits = true;
+
+		{
+			let mut board = self.board.borrow_mut();
+			for _ in 0..rows {
+				let row = self.garbage.next_row(board.cols());
+				fits &= board.add_garbage(row, false);
+			}
+		}
+
+		if !fits {
+			self.game_over(TopOut::Garbage);
+		}
+
+		fits
+	}
+
+	/// A practice game goes back to before the placement that topped out
+	/// instead of ending
+	fn game_over(&mut self, top_out: TopOut) {
+		if let GameMode::Practice {
+			..
+		} = self.mode
+		{
+			if let Some(snapshot) = self.history.drop_last() {
+				self.spawn_at = None;
+				self.initial_rotation = None;
+				self.initial_hold = false;
+				self.restore(snapshot);
+				self.handler.reset_gravity();
+				return;
+			}
+		}
+
+		self.end_game(false);
+		self.game_over_menu.add_top_out(top_out);
+	}
+
+	/// `finished` is true when the goal of the mode is reached
+	fn end_game(&mut self, finished: bool) {
+		self.stopwatch.stop();
 		self.focus.push(Scene::GameOverMenu);
-		self.handler.pause();
+		self.pause();
 		self.handler.cancel_grvity();
 		self.handler.cancel_lock();
-		let idx = self.scores.push_new_score(self.stats.score);
-		self.game_over_menu.set_new_score(self.stats.score, idx);
+
+		let record = self.score_record();
+
+		match self.mode {
+			GameMode::Marathon => {
+				let idx = self.leaderboards_mut().scores.push_new_score(record);
+				if finished {
+					self.game_over_menu.set_victory(self.stats.score, idx);
+				} else {
+					self.game_over_menu.set_new_score(self.stats.score, idx);
+				}
+			}
+			GameMode::Endless => {
+				let idx = self
+					.leaderboards_mut()
+					.endless_scores
+					.push_new_score(record);
+				self.game_over_menu.set_new_score(self.stats.score, idx);
+			}
+			GameMode::Sprint {
+				lines,
+			} => {
+				if finished {
+					let time = self.stopwatch.elapsed().as_millis() as u64;
+					let times = &mut self.leaderboards_mut().sprint_times;
+					let best = times.best(lines);
+					let idx = times.push_new_time(lines, time);
+					self.game_over_menu.set_new_time(time, idx, best);
+				} else {
+					self.game_over_menu.set_no_record();
+				}
+			}
+			GameMode::Ultra {
+				minutes,
+			} => {
+				if finished {
+					let idx = self
+						.leaderboards_mut()
+						.ultra_scores
+						.push_new_score(minutes, record);
+					self.game_over_menu.set_time_up(self.stats.score, idx);
+				} else {
+					self.game_over_menu.set_no_record();
+				}
+			}
+			GameMode::Dig {
+				rows,
+			} => {
+				if finished {
+					let record = DigRecord {
+						time: self.stopwatch.elapsed().as_millis() as u64,
+						pieces: self.stats.pieces,
+					};
+					let records = &mut self.leaderboards_mut().dig_records;
+					let best = records.best(rows);
+					let idx = records.push_new_record(rows, record);
+					self.game_over_menu.set_new_dig_time(
+						record.time,
+						record.pieces,
+						idx,
+						best,
+					);
+				} else {
+					self.game_over_menu.set_no_record();
+				}
+			}
+			// Practice games are never recorded
+			GameMode::Practice {
+				..
+			} => self.game_over_menu.set_no_record(),
+			GameMode::Puzzle {
+				..
+			} => {
+				if finished {
+					if let Some(puzzle) = &self.puzzle {
+						self.puzzle_menu.solved.insert(puzzle.id.clone());
+					}
+				}
+				self.game_over_menu.set_puzzle_result(
+					finished,
+					self.stats.pieces,
+					self.stopwatch.elapsed().as_millis() as u64,
+				);
+			}
+		}
+
+		if let Some(grade) = self.stats.grade() {
+			self.game_over_menu.add_grade(grade);
+		}
 
 		global_audio(|audio| {
 			audio.stop_music();
@@ -302,24 +944,94 @@ impl State {
 		});
 	}
 
+	fn score_record(&self) -> ScoreRecord {
+		ScoreRecord {
+			score: self.stats.score,
+			start_level: self.stats.start_level,
+		}
+	}
+
+	fn hold(&mut self) {
+		if self.hold_board.used {
+			return;
+		}
+
+		let kind = self.alive_tetromino.kind;
+		let held = self.hold_board.current;
+
+		// Nothing to swap in once a fixed sequence is used up
+		if held.is_none() && self.next_board.is_empty() {
+			return;
+		}
+
+		self.handler.cancel_lock();
+		self.hold_board.set_hold(kind);
+
+		global_audio(|audio| audio.play_sound(Sound::Move));
+
+		if let Some(held) = held {
+			self.spawn_tetromino(held);
+		} else {
+			self.next_alive_tetromino();
+		}
+	}
+
 	fn next_alive_tetromino(&mut self) {
-		self.alive_tetromino.set_next(self.next_board.current);
-		self.next_board.set_next(self.bag.next());
+		let Some(kind) = self.next_board.next(&mut self.bag) else {
+			// Out of pieces
+			self.end_game(false);
+			return;
+		};
+
+		if std::mem::take(&mut self.initial_hold)
+			&& (self.hold_board.current.is_some()
+				|| !self.next_board.is_empty())
+		{
+			let held = self.hold_board.current;
+
+			self.hold_board.set_hold(kind);
+
+			global_audio(|audio| audio.play_sound(Sound::Move));
+
+			match held {
+				Some(held) => self.spawn_tetromino(held),
+				None => self.next_alive_tetromino(),
+			}
+			return;
+		}
+
+		self.spawn_tetromino(kind);
+	}
+
+	fn spawn_tetromino(&mut self, kind: TetrominoKind) {
+		self.alive_tetromino.set_next(kind);
+
+		// The piece enters unrotated when the rotation does not fit
+		if let Some(rotation) = self.initial_rotation.take() {
+			self.alive_tetromino.rotate(rotation);
+		}
+
+		if global_setting().spawn_up() && self.is_spawn_blocked() {
+			self.alive_tetromino.raise();
+		}
+
 		self.update_ghost_tetromino();
 		self.check_lock();
 
-		if self
-			.board
-			.borrow()
-			.is_collision(&self.alive_tetromino.position)
-		{
-			self.game_over();
+		if self.is_spawn_blocked() {
+			self.game_over(TopOut::Block);
 			return;
 		}
 
 		self.handler.reset_gravity();
 	}
 
+	fn is_spawn_blocked(&self) -> bool {
+		self.board
+			.borrow()
+			.is_collision(&self.alive_tetromino.position)
+	}
+
 	fn handle_pause_menu(&mut self, event: Event) {
 		use pause_menu_idx::*;
 
@@ -330,7 +1042,7 @@ impl State {
 				match self.pause_menu.cursor() {
 					RESUME => {
 						self.focus.back();
-						self.handler.cancel_pause();
+						self.resume();
 						self.pause_menu.reset();
 					}
 					NEW_GAME => {
@@ -342,13 +1054,17 @@ impl State {
 					SCORES => self.focus.push(Scene::Scores),
 					SETTING => self.focus.push(Scene::SettingMenu),
 					HELP => self.focus.push(Scene::Help),
+					MAIN_MENU => {
+						self.pause_menu.reset();
+						self.main_menu();
+					}
 					QUIT => self.running = false,
 					_ => (),
 				}
 			}
 			Event::Esc => {
 				self.focus.back();
-				self.handler.cancel_pause();
+				self.resume();
 				self.pause_menu.reset();
 			}
 			_ => (),
@@ -359,7 +1075,10 @@ impl State {
 		match event {
 			Event::Up => self.setting_menu.up(),
 			Event::Down => self.setting_menu.down(),
-			Event::Enter => self.setting_menu.handle_enter(),
+			Event::Left => self.setting_menu.handle_change(false),
+			Event::Right | Event::Enter => {
+				self.setting_menu.handle_change(true)
+			}
 			Event::Esc => {
 				self.focus.back();
 				self.setting_menu.reset();
@@ -381,6 +1100,10 @@ impl State {
 						self.game_over_menu.reset();
 					}
 					SCORES => self.focus.push(Scene::Scores),
+					MAIN_MENU => {
+						self.game_over_menu.reset();
+						self.main_menu();
+					}
 					QUIT => self.running = false,
 					_ => (),
 				}
@@ -389,12 +1112,63 @@ impl State {
 		}
 	}
 
+	pub fn update_stopwatch(&mut self) {
+		if !self.focus.contains(Scene::Game) {
+			return;
+		}
+
+		self.stopwatch.update();
+
+		if let GameMode::Dig {
+			..
+		} = self.mode
+		{
+			self.garbage.update(self.stopwatch.elapsed());
+		}
+
+		// A running line clear ends the game once it is done
+		if *self.focus.current() == Scene::Game
+			&& !self.board.borrow().line_clear.in_progress
+			&& self.mode.is_finished(&self.stats, &self.stopwatch)
+		{
+			self.end_game(true);
+		}
+	}
+
 	pub fn update_line_clear(&mut self) {
 		self.board.borrow_mut().particles.update();
 		if !self.board.borrow().line_clear.in_progress {
 			return;
 		}
-		if self.board.borrow_mut().update_line_clear() {
+
+		let delay =
+			Duration::from_millis(global_setting().line_clear_delay() as u64);
+
+		if self
+			.board
+			.borrow_mut()
+			.update_line_clear(self.stopwatch.clock(), delay)
+		{
+			if self.board.borrow().is_empty() {
+				self.stats.perfect_clear();
+			}
+			if self.mode.is_finished(&self.stats, &self.stopwatch) {
+				self.end_game(true);
+				return;
+			}
+			self.prepare_next_tetromino();
+		}
+	}
+
+	pub fn update_entry_delay(&mut self) {
+		let Some(spawn_at) = self.spawn_at else {
+			return;
+		};
+
+		if *self.focus.current() == Scene::Game
+			&& self.stopwatch.clock() >= spawn_at
+		{
+			self.spawn_at = None;
 			self.next_alive_tetromino();
 		}
 	}
@@ -0,0 +1,145 @@
+use super::{game_mode::GameMode, modifiers::Modifiers};
+use crate::{
+	common::{Menu, VecExt},
+	global::{global_audio, global_setting, Sound},
+};
+
+pub struct ModeMenu {
+	items: Vec<String>,
+	cursor: usize,
+}
+
+impl ModeMenu {
+	pub fn new() -> Self {
+		Self {
+			items: vec![
+				"MARATHON",
+				"ENDLESS",
+				"SPRINT",
+				"ULTRA",
+				"DIG",
+				"PUZZLE",
+				"PRACTICE",
+				"EDITOR",
+				"MODIFIERS",
+			]
+			.into_owned_vec(),
+			cursor: 0,
+		}
+	}
+
+	/// Change the goal of the selected mode
+	pub fn handle_change(&self, forward: bool) {
+		use mode_menu_idx::*;
+
+		let setting = global_setting();
+
+		match self.cursor {
+			MARATHON | ENDLESS => setting.change_start_level(forward),
+			SPRINT => setting.change_sprint_lines(forward),
+			ULTRA => setting.change_ultra_minutes(forward),
+			DIG => setting.change_dig_rows(forward),
+			PRACTICE => setting.switch_practice_gravity(),
+			_ => return,
+		}
+
+		global_audio(|audio| audio.play_sound(Sound::Menu));
+	}
+
+	pub fn mode(&self) -> GameMode {
+		use mode_menu_idx::*;
+
+		let setting = global_setting();
+
+		match self.cursor {
+			ENDLESS => GameMode::Endless,
+			SPRINT => {
+				GameMode::Sprint {
+					lines: setting.sprint_lines(),
+				}
+			}
+			ULTRA => {
+				GameMode::Ultra {
+					minutes: setting.ultra_minutes(),
+				}
+			}
+			DIG => {
+				GameMode::Dig {
+					rows: dig_rows() as u32,
+				}
+			}
+			PRACTICE => {
+				GameMode::Practice {
+					gravity: setting.practice_gravity(),
+				}
+			}
+			_ => GameMode::Marathon,
+		}
+	}
+}
+
+impl Menu for ModeMenu {
+	fn cursor_mut(&mut self) -> &mut usize {
+		&mut self.cursor
+	}
+
+	fn cursor(&self) -> usize {
+		self.cursor
+	}
+
+	fn end(&self) -> usize {
+		self.items.len() - 1
+	}
+
+	fn items(&self) -> Vec<String> {
+		use mode_menu_idx::*;
+
+		let setting = global_setting();
+
+		self.items
+			.iter()
+			.enumerate()
+			.map(|(i, label)| {
+				match i {
+					MARATHON | ENDLESS => {
+						format!("{} LV{}", label, setting.start_level())
+					}
+					SPRINT => format!("{} {}L", label, setting.sprint_lines()),
+					ULTRA => {
+						format!("{} {}MIN", label, setting.ultra_minutes())
+					}
+					DIG => format!("{} {}L", label, dig_rows()),
+					// Zero gravity
+					PRACTICE if !setting.practice_gravity() => {
+						format!("{} 0G", label)
+					}
+					_ => label.clone(),
+				}
+			})
+			.collect()
+	}
+}
+
+/// Garbage rows of the dig mode, a few rows are left free at the top on
+/// short boards
+pub fn dig_rows() -> usize {
+	let setting = global_setting();
+	let (_, rows) = Modifiers::from_setting()
+		.board_size(setting.board_cols(), setting.board_visible_rows());
+
+	setting.dig_rows().min(rows.saturating_sub(DIG_FREE_ROWS))
+}
+
+const DIG_FREE_ROWS: usize = 4;
+
+pub mod mode_menu_idx {
+	pub const MARATHON: usize = 0;
+	pub const ENDLESS: usize = 1;
+	pub const SPRINT: usize = 2;
+	pub const ULTRA: usize = 3;
+	pub const DIG: usize = 4;
+	pub const PUZZLE: usize = 5;
+	pub const PRACTICE: usize = 6;
+	pub const EDITOR: usize = 7;
+	pub const MODIFIERS: usize = 8;
+}
@@ -0,0 +1,100 @@
+use super::setting_menu::{bool_text, item_text};
+use crate::{
+	common::{Menu, VecExt},
+	global::{global_audio, global_setting, setting::StackVisibility, Sound},
+};
+
+pub struct ModifierMenu {
+	items: Vec<String>,
+	cursor: usize,
+}
+
+impl ModifierMenu {
+	pub fn new() -> Self {
+		Self {
+			items: vec![
+				"MIRROR",
+				"NO GHOST",
+				"NO NEXT",
+				"MONOCHROME",
+				"BIG",
+				"STACK",
+			]
+			.into_owned_vec(),
+			cursor: 0,
+		}
+	}
+
+	pub fn handle_change(&self, forward: bool) {
+		use modifier_menu_idx::*;
+
+		let setting = global_setting();
+
+		match self.cursor {
+			MIRROR => setting.switch_mirror(),
+			NO_GHOST => setting.switch_no_ghost(),
+			NO_NEXT => setting.switch_no_next(),
+			MONOCHROME => setting.switch_monochrome(),
+			BIG => setting.switch_big(),
+			STACK => setting.change_stack(forward),
+			_ => (),
+		}
+
+		global_audio(|audio| audio.play_sound(Sound::Menu));
+	}
+}
+
+impl Menu for ModifierMenu {
+	fn cursor_mut(&mut self) -> &mut usize {
+		&mut self.cursor
+	}
+
+	fn cursor(&self) -> usize {
+		self.cursor
+	}
+
+	fn end(&self) -> usize {
+		self.items.len() - 1
+	}
+
+	fn items(&self) -> Vec<String> {
+		use modifier_menu_idx::*;
+
+		let setting = global_setting();
+
+		self.items
+			.iter()
+			.enumerate()
+			.map(|(i, label)| {
+				let value = match i {
+					MIRROR => bool_text(setting.mirror()),
+					NO_GHOST => bool_text(setting.no_ghost()),
+					NO_NEXT => bool_text(setting.no_next()),
+					MONOCHROME => bool_text(setting.monochrome()),
+					BIG => bool_text(setting.big()),
+					STACK => stack_text(setting.stack()),
+					_ => String::new(),
+				};
+				item_text(label, value)
+			})
+			.collect()
+	}
+}
+
+mod modifier_menu_idx {
+	pub const MIRROR: usize = 0;
+	pub const NO_GHOST: usize = 1;
+	pub const NO_NEXT: usize = 2;
+	pub const MONOCHROME: usize = 3;
+	pub const BIG: usize = 4;
+	pub const STACK: usize = 5;
+}
+
+fn stack_text(v: StackVisibility) -> String {
+	match v {
+		StackVisibility::Normal => "NORMAL",
+		StackVisibility::Fading => "FADING",
+		StackVisibility::Invisible => "INVISIBLE",
+	}
+	.to_string()
+}
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	common::TetrominoKind,
+	consts::{MAIN_BOARD_MIN_COLS, MAIN_BOARD_MIN_VISIBLE_ROWS},
+	global::{global_setting, setting::StackVisibility},
+};
+
+/// Modifiers a game was started with, chosen in the modifier menu
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Modifiers {
+	pub mirror: bool,
+	pub no_ghost: bool,
+	pub no_next: bool,
+	pub monochrome: bool,
+	pub big: bool,
+	pub stack: StackVisibility,
+}
+
+impl Modifiers {
+	pub fn from_setting() -> Self {
+		let setting = global_setting();
+
+		Self {
+			mirror: setting.mirror(),
+			no_ghost: setting.no_ghost(),
+			no_next: setting.no_next(),
+			monochrome: setting.monochrome(),
+			big: setting.big(),
+			stack: setting.stack(),
+		}
+	}
+
+	/// Scores of modified games are kept apart under this tag, it is empty
+	/// without modifiers
+	pub fn tag(&self) -> String {
+		let mut tags = Vec::new();
+
+		if self.mirror {
+			tags.push("MIRROR");
+		}
+		if self.no_ghost {
+			tags.push("NO GHOST");
+		}
+		if self.no_next {
+			tags.push("NO NEXT");
+		}
+		if self.monochrome {
+			tags.push("MONO");
+		}
+		if self.big {
+			tags.push("BIG");
+		}
+		match self.stack {
+			StackVisibility::Normal => (),
+			StackVisibility::Fading => tags.push("FADING"),
+			StackVisibility::Invisible => tags.push("INVISIBLE"),
+		}
+
+		tags.join(" ")
+	}
+
+	/// Columns and visible rows of the board the pieces move on, a big game
+	/// is played on half of the board in each direction but never on less
+	/// than the smallest board
+	pub fn board_size(&self, cols: usize, rows: usize) -> (usize, usize) {
+		if self.big {
+			(
+				(cols / 2).max(MAIN_BOARD_MIN_COLS),
+				(rows / 2).max(MAIN_BOARD_MIN_VISIBLE_ROWS),
+			)
+		} else {
+			(cols, rows)
+		}
+	}
+
+	/// Cells on the screen for every cell of the board
+	pub fn scale(&self) -> u16 {
+		if self.big {
+			2
+		} else {
+			1
+		}
+	}
+
+	/// The kind whose colors a cell is drawn with
+	pub fn shown_kind<'a>(&self, kind: &'a TetrominoKind) -> &'a TetrominoKind {
+		if self.monochrome {
+			&TetrominoKind::Garbage
+		} else {
+			kind
+		}
+	}
+}
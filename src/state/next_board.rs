@@ -1,47 +1,56 @@
+use std::{
+	collections::{vec_deque::Iter, VecDeque},
+	iter::Take,
+};
+
 use serde::{Deserialize, Serialize};
 
+use super::bag::Bag;
 use crate::{
-	common::{Board, Reset, TetrominoKind},
-	consts::{NEXT_BOARD_COLS, NEXT_BOARD_ROWS},
+	common::{Reset, TetrominoKind},
+	consts::NEXT_QUEUE_MAX_LEN,
+	global::global_setting,
 };
 
+/// The queue is always kept full so that the piece sequence does not depend
+/// on how many previews are shown, only a fixed sequence can run out
 #[derive(Clone, Deserialize, Serialize)]
 pub struct NextBoard {
-	cells: Vec<Vec<Option<TetrominoKind>>>,
-	pub current: TetrominoKind,
+	#[serde(default)]
+	queue: VecDeque<TetrominoKind>,
 }
 
 impl NextBoard {
 	pub fn new() -> Self {
 		Self {
-			cells: vec![vec![None; NEXT_BOARD_COLS]; NEXT_BOARD_ROWS],
-			current: TetrominoKind::default(),
+			queue: VecDeque::with_capacity(NEXT_QUEUE_MAX_LEN),
 		}
 	}
 
-	pub fn set_next(&mut self, kind: TetrominoKind) {
-		self.current = kind;
-
-		for line in &mut self.cells {
-			for cell in line {
-				if cell.is_some() {
-					*cell = None;
-				}
-			}
+	pub fn fill(&mut self, bag: &mut Bag) {
+		while self.queue.len() < NEXT_QUEUE_MAX_LEN {
+			let Some(kind) = bag.next() else {
+				break;
+			};
+			self.queue.push_back(kind);
 		}
+	}
 
-		let mut position = kind.init_position(0);
-		position.update(|p| p.x += 3);
+	/// `None` when the bag has run out of pieces
+	pub fn next(&mut self, bag: &mut Bag) -> Option<TetrominoKind> {
+		self.fill(bag);
+		let kind = self.queue.pop_front();
+		self.fill(bag);
 
-		for p in position.to_usize_points() {
-			self.cells[p.y][p.x] = Some(kind);
-		}
+		kind
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.queue.is_empty()
 	}
-}
 
-impl Board for NextBoard {
-	fn get_kind(&self, x: usize, y: usize) -> Option<&TetrominoKind> {
-		self.cells[y][x].as_ref()
+	pub fn visible(&self) -> Take<Iter<TetrominoKind>> {
+		self.queue.iter().take(global_setting().next_count())
 	}
 }
 
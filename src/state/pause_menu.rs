@@ -9,7 +9,13 @@ impl PauseMenu {
 	pub fn new() -> Self {
 		Self {
 			items: vec![
-				"RESUME", "NEW GAME", "SCORES", "SETTING", "HELP", "QUIT",
+				"RESUME",
+				"NEW GAME",
+				"SCORES",
+				"SETTING",
+				"HELP",
+				"MAIN MENU",
+				"QUIT",
 			]
 			.into_owned_vec(),
 			cursor: 0,
@@ -41,5 +47,6 @@ pub mod pause_menu_idx {
 	pub const SCORES: usize = 2;
 	pub const SETTING: usize = 3;
 	pub const HELP: usize = 4;
-	pub const QUIT: usize = 5;
+	pub const MAIN_MENU: usize = 5;
+	pub const QUIT: usize = 6;
 }
@@ -0,0 +1,238 @@
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	common::TetrominoKind,
+	consts::{
+		MAIN_BOARD_MAX_COLS, MAIN_BOARD_MAX_VISIBLE_ROWS, MAIN_BOARD_MIN_COLS,
+	},
+	global::global_setting,
+};
+
+/// A hand-made challenge read from a JSON or TOML file
+///
+/// ```toml
+/// name = "T-SPIN DOUBLE"
+/// objective = "t_spin_double"
+/// pieces = "TIO"
+/// board = [
+///   "GGGG..GGGG",
+///   "GGGGG.GGGG",
+/// ]
+/// ```
+///
+/// The rows of the board are listed from top to bottom and sit at the bottom
+/// of the board, `.` is an empty cell, a kind letter is a cell of that kind
+/// and `G` is garbage. Without `pieces` the randomizer from the setting deals
+/// the pieces. The objective is `"clear_all"`, `"t_spin_double"` or
+/// `{ survive = 10 }`.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Puzzle {
+	/// File name without the extension, solved puzzles are tracked by it
+	pub id: String,
+	pub name: String,
+	pub objective: Objective,
+	#[serde(flatten)]
+	pub setup: Setup,
+}
+
+/// Cells a game starts with and the pieces it deals, from a puzzle or the
+/// board editor
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct Setup {
+	/// Dealt in order first, a puzzle ends when they run out and a board
+	/// from the editor goes on with the randomizer
+	pub pieces: Vec<TetrominoKind>,
+	/// Rows from top to bottom, they sit at the bottom of the board
+	pub cells: Vec<Vec<Option<TetrominoKind>>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Objective {
+	/// Leave the board empty
+	ClearAll,
+	TSpinDouble,
+	/// Lock this many pieces without topping out
+	Survive(u32),
+}
+
+impl Objective {
+	pub fn text(&self) -> String {
+		match self {
+			Objective::ClearAll => "CLEAR ALL LINES".to_string(),
+			Objective::TSpinDouble => "T-SPIN DOUBLE".to_string(),
+			Objective::Survive(pieces) => format!("SURVIVE {} PIECES", pieces),
+		}
+	}
+}
+
+#[derive(Deserialize)]
+struct PuzzleFile {
+	name: Option<String>,
+	objective: Objective,
+	#[serde(flatten)]
+	setup: SetupFile,
+}
+
+#[derive(Deserialize)]
+struct SetupFile {
+	#[serde(default)]
+	pieces: String,
+	board: Vec<String>,
+}
+
+impl Puzzle {
+	pub fn from_json(id: &str, content: &str) -> Result<Self> {
+		Self::from_file(id, serde_json::from_str(content)?)
+	}
+
+	pub fn from_toml(id: &str, content: &str) -> Result<Self> {
+		Self::from_file(id, toml::from_str(content).map_err(toml_reason)?)
+	}
+
+	fn from_file(id: &str, file: PuzzleFile) -> Result<Self> {
+		Ok(Self {
+			id: id.to_string(),
+			name: file.name.unwrap_or(id.to_string()).to_uppercase(),
+			objective: file.objective,
+			setup: Setup::from_file(file.setup)?,
+		})
+	}
+}
+
+impl Setup {
+	/// Checks that the cells make a board a game can be played on
+	pub fn new(
+		pieces: Vec<TetrominoKind>,
+		cells: Vec<Vec<Option<TetrominoKind>>>,
+	) -> Result<Self> {
+		let cols = cells.first().map_or(0, |row| row.len());
+
+		if !(MAIN_BOARD_MIN_COLS..=MAIN_BOARD_MAX_COLS).contains(&cols) {
+			bail!(
+				"the board should be {} to {} cells wide",
+				MAIN_BOARD_MIN_COLS,
+				MAIN_BOARD_MAX_COLS
+			);
+		}
+		if cells.iter().any(|row| row.len() != cols) {
+			bail!("every row of the board should be as wide as the first");
+		}
+		if cells.len() + PUZZLE_FREE_ROWS > MAIN_BOARD_MAX_VISIBLE_ROWS {
+			bail!(
+				"the board can have at most {} rows",
+				MAIN_BOARD_MAX_VISIBLE_ROWS - PUZZLE_FREE_ROWS
+			);
+		}
+		if cells
+			.iter()
+			.any(|row| row.iter().all(|cell| cell.is_some()))
+		{
+			bail!("the board should not have full rows");
+		}
+
+		Ok(Self {
+			pieces,
+			cells,
+		})
+	}
+
+	/// Reads a board saved by the board editor, it is a puzzle file without
+	/// the name and the objective
+	pub fn from_toml(content: &str) -> Result<Self> {
+		Self::from_file(toml::from_str(content)?)
+	}
+
+	fn from_file(file: SetupFile) -> Result<Self> {
+		let pieces = file
+			.pieces
+			.chars()
+			.filter(|c| !c.is_whitespace())
+			.map(|c| {
+				match kind_from_char(c)? {
+					Some(TetrominoKind::Garbage) | None => {
+						Err(anyhow!("`{}` is not a piece", c))
+					}
+					Some(kind) => Ok(kind),
+				}
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		let cells = file
+			.board
+			.iter()
+			.map(|row| row.chars().map(kind_from_char).collect())
+			.collect::<Result<Vec<Vec<_>>>>()?;
+
+		Self::new(pieces, cells)
+	}
+
+	/// The board in the format [`Setup::from_toml`] reads
+	pub fn to_toml(&self) -> String {
+		let pieces: String = self.pieces.iter().map(kind_to_char).collect();
+		let rows: Vec<String> = self
+			.cells
+			.iter()
+			.map(|row| {
+				let row: String = row
+					.iter()
+					.map(|cell| cell.as_ref().map_or('.', kind_to_char))
+					.collect();
+				format!("  \"{}\",\n", row)
+			})
+			.collect();
+
+		format!("pieces = \"{}\"\nboard = [\n{}]\n", pieces, rows.concat())
+	}
+
+	/// Columns and visible rows, the height of the setting is used when the
+	/// layout fits in it
+	pub fn board_size(&self) -> (usize, usize) {
+		let cols = self.cells.first().map_or(0, |row| row.len());
+		let rows = global_setting()
+			.board_visible_rows()
+			.max(self.cells.len() + PUZZLE_FREE_ROWS);
+
+		(cols, rows)
+	}
+}
+
+/// Only the reason of a TOML error on one line, the full error quotes the
+/// file over several lines
+fn toml_reason(e: toml::de::Error) -> anyhow::Error {
+	anyhow!("{}", e.message().lines().collect::<Vec<_>>().join(", "))
+}
+
+/// Empty rows kept above the layout
+pub const PUZZLE_FREE_ROWS: usize = 4;
+
+fn kind_from_char(c: char) -> Result<Option<TetrominoKind>> {
+	let kind = match c.to_ascii_uppercase() {
+		'.' | ' ' | '_' => return Ok(None),
+		'I' => TetrominoKind::I,
+		'J' => TetrominoKind::J,
+		'L' => TetrominoKind::L,
+		'O' => TetrominoKind::O,
+		'S' => TetrominoKind::S,
+		'T' => TetrominoKind::T,
+		'Z' => TetrominoKind::Z,
+		'G' | '#' | 'X' => TetrominoKind::Garbage,
+		_ => bail!("unknown cell `{}`", c),
+	};
+
+	Ok(Some(kind))
+}
+
+pub fn kind_to_char(kind: &TetrominoKind) -> char {
+	match kind {
+		TetrominoKind::I => 'I',
+		TetrominoKind::J => 'J',
+		TetrominoKind::L => 'L',
+		TetrominoKind::O => 'O',
+		TetrominoKind::S => 'S',
+		TetrominoKind::T => 'T',
+		TetrominoKind::Z => 'Z',
+		TetrominoKind::Garbage => 'G',
+	}
+}
@@ -0,0 +1,62 @@
+use std::{collections::BTreeSet, path::PathBuf};
+
+use super::puzzle::Puzzle;
+use crate::common::Menu;
+
+pub struct PuzzleMenu {
+	pub puzzles: Vec<Puzzle>,
+	/// Files that failed to parse, as `file name: reason`
+	pub errors: Vec<String>,
+	/// Ids of the solved puzzles
+	pub solved: BTreeSet<String>,
+	/// Where the puzzle files are read from
+	pub dir: Option<PathBuf>,
+	cursor: usize,
+}
+
+impl PuzzleMenu {
+	pub fn new() -> Self {
+		Self {
+			puzzles: Vec::new(),
+			errors: Vec::new(),
+			solved: BTreeSet::new(),
+			dir: None,
+			cursor: 0,
+		}
+	}
+
+	pub fn current(&self) -> Option<&Puzzle> {
+		self.puzzles.get(self.cursor)
+	}
+}
+
+impl Menu for PuzzleMenu {
+	fn cursor_mut(&mut self) -> &mut usize {
+		&mut self.cursor
+	}
+
+	fn cursor(&self) -> usize {
+		self.cursor
+	}
+
+	fn end(&self) -> usize {
+		self.puzzles.len().saturating_sub(1)
+	}
+
+	fn items(&self) -> Vec<String> {
+		self.puzzles
+			.iter()
+			.map(|puzzle| {
+				let name: String =
+					puzzle.name.chars().take(PUZZLE_NAME_MAX_LEN).collect();
+				if self.solved.contains(&puzzle.id) {
+					format!("{} *", name)
+				} else {
+					name
+				}
+			})
+			.collect()
+	}
+}
+
+const PUZZLE_NAME_MAX_LEN: usize = 14;
@@ -0,0 +1,164 @@
+use fastrand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{common::TetrominoKind, global::setting::RandomizerKind};
+
+/// Piece generator together with whatever it has to remember between
+/// pieces, the random numbers come from the seeded RNG of the [`Bag`]
+///
+/// [`Bag`]: super::bag::Bag
+#[derive(Clone, Deserialize, Serialize)]
+pub enum Randomizer {
+	/// Deals `copies` of every kind in a random order before refilling
+	Bag {
+		copies: usize,
+		kinds: Vec<TetrominoKind>,
+		cursor: usize,
+	},
+	/// Every kind is equally likely every time
+	Random,
+	/// Rerolls once when the first roll repeats the last piece
+	NesReroll { last: Option<TetrominoKind> },
+	/// Rolls up to 6 times for a kind that is not in the last 4 pieces
+	TgmHistory { history: Vec<TetrominoKind> },
+	/// Deals a fixed list of pieces and then goes on with `then`, or runs
+	/// out without it
+	Sequence {
+		kinds: Vec<TetrominoKind>,
+		cursor: usize,
+		#[serde(default)]
+		then: Option<Box<Randomizer>>,
+	},
+}
+
+impl Default for Randomizer {
+	fn default() -> Self {
+		Self::new(RandomizerKind::default())
+	}
+}
+
+impl Randomizer {
+	pub fn new(kind: RandomizerKind) -> Self {
+		match kind {
+			RandomizerKind::SevenBag => Self::bag(1),
+			RandomizerKind::FourteenBag => Self::bag(2),
+			RandomizerKind::Random => Self::Random,
+			RandomizerKind::NesReroll => {
+				Self::NesReroll {
+					last: None,
+				}
+			}
+			RandomizerKind::TgmHistory => {
+				Self::TgmHistory {
+					history: Vec::new(),
+				}
+			}
+		}
+	}
+
+	fn bag(copies: usize) -> Self {
+		Self::Bag {
+			copies,
+			kinds: Vec::with_capacity(TetrominoKind::ALL.len() * copies),
+			cursor: 0,
+		}
+	}
+
+	/// `None` once a [`Randomizer::Sequence`] without `then` has run out
+	pub fn next(&mut self, rng: &mut Rng) -> Option<TetrominoKind> {
+		let kind = match self {
+			Self::Bag {
+				copies,
+				kinds,
+				cursor,
+			} => {
+				if *cursor >= kinds.len() {
+					kinds.clear();
+					for _ in 0..*copies {
+						kinds.extend(TetrominoKind::ALL);
+					}
+					rng.shuffle(kinds);
+					*cursor = 0;
+				}
+				let kind = kinds[*cursor];
+				*cursor += 1;
+
+				kind
+			}
+			Self::Random => roll(rng),
+			Self::NesReroll {
+				last,
+			} => {
+				// The eighth outcome of the first roll forces a reroll too
+				let idx = rng.usize(0..=TetrominoKind::ALL.len());
+				let mut kind = TetrominoKind::ALL.get(idx).copied();
+				if kind.is_none() || kind == *last {
+					kind = Some(roll(rng));
+				}
+				*last = kind;
+
+				kind.unwrap()
+			}
+			Self::TgmHistory {
+				history,
+			} => {
+				const HISTORY_LEN: usize = 4;
+				const ROLLS: usize = 6;
+
+				let kind = if history.is_empty() {
+					// The first piece is never S, Z or O
+					*rng.choice(&[
+						TetrominoKind::I,
+						TetrominoKind::J,
+						TetrominoKind::L,
+						TetrominoKind::T,
+					])
+					.unwrap()
+				} else {
+					let mut kind = roll(rng);
+					for _ in 1..ROLLS {
+						if !history.contains(&kind) {
+							break;
+						}
+						kind = roll(rng);
+					}
+					kind
+				};
+
+				if history.is_empty() {
+					// The history starts as Z S S Z, the first piece pushes
+					// out the oldest Z
+					history.extend([
+						TetrominoKind::S,
+						TetrominoKind::S,
+						TetrominoKind::Z,
+					]);
+				}
+				history.push(kind);
+				if history.len() > HISTORY_LEN {
+					history.remove(0);
+				}
+
+				kind
+			}
+			Self::Sequence {
+				kinds,
+				cursor,
+				then,
+			} => {
+				let Some(kind) = kinds.get(*cursor).copied() else {
+					return then.as_mut()?.next(rng);
+				};
+				*cursor += 1;
+
+				kind
+			}
+		};
+
+		Some(kind)
+	}
+}
+
+fn roll(rng: &mut Rng) -> TetrominoKind {
+	TetrominoKind::ALL[rng.usize(..TetrominoKind::ALL.len())]
+}
@@ -1,24 +1,240 @@
-use std::slice::Iter;
+use std::{collections::BTreeMap, slice::Iter};
 
 use serde::{Deserialize, Serialize};
 
+use super::modifiers::Modifiers;
+use crate::global::setting::ScoringSystem;
+
+/// Key of the leaderboards a game is recorded in, it is empty for games with
+/// the guideline scoring and without modifiers
+pub fn leaderboard_tag(
+	modifiers: &Modifiers,
+	scoring: ScoringSystem,
+) -> String {
+	let scoring = match scoring {
+		ScoringSystem::Guideline => "",
+		ScoringSystem::Nes => "NES SCORING",
+		ScoringSystem::Tgm => "TGM SCORING",
+	};
+
+	[modifiers.tag().as_str(), scoring]
+		.into_iter()
+		.filter(|tag| !tag.is_empty())
+		.collect::<Vec<_>>()
+		.join(" ")
+}
+
+/// Every leaderboard of the game, games with modifiers have their own
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Leaderboards {
+	#[serde(default = "Scores::new")]
+	pub scores: Scores,
+	#[serde(default = "Scores::new")]
+	pub endless_scores: Scores,
+	#[serde(default)]
+	pub sprint_times: SprintTimes,
+	#[serde(default)]
+	pub ultra_scores: UltraScores,
+	#[serde(default)]
+	pub dig_records: DigRecords,
+}
+
+impl Default for Leaderboards {
+	fn default() -> Self {
+		Self {
+			scores: Scores::new(),
+			endless_scores: Scores::new(),
+			sprint_times: SprintTimes::default(),
+			ultra_scores: UltraScores::default(),
+			dig_records: DigRecords::default(),
+		}
+	}
+}
+
 #[derive(Clone, Deserialize, Serialize)]
 #[serde(transparent)]
-pub struct Scores(Vec<u32>);
+pub struct Scores(Vec<ScoreRecord>);
+
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "ScoreRecordRepr")]
+pub struct ScoreRecord {
+	pub score: u32,
+	pub start_level: u32,
+}
+
+/// Older saves only have the score
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ScoreRecordRepr {
+	Score(u32),
+	Record { score: u32, start_level: u32 },
+}
+
+impl From<ScoreRecordRepr> for ScoreRecord {
+	fn from(repr: ScoreRecordRepr) -> Self {
+		match repr {
+			ScoreRecordRepr::Score(score) => {
+				Self {
+					score,
+					start_level: 1,
+				}
+			}
+			ScoreRecordRepr::Record {
+				score,
+				start_level,
+			} => {
+				Self {
+					score,
+					start_level,
+				}
+			}
+		}
+	}
+}
 
 impl Scores {
 	pub fn new() -> Self {
-		Self(vec![0; 10])
+		Self(vec![
+			ScoreRecord {
+				score: 0,
+				start_level: 1,
+			};
+			10
+		])
 	}
 
-	pub fn push_new_score(&mut self, new_score: u32) -> Option<usize> {
+	pub fn push_new_score(&mut self, new_score: ScoreRecord) -> Option<usize> {
 		self.0.push(new_score);
-		self.0.sort_unstable_by(|a, b| b.cmp(a));
+		self.0.sort_by(|a, b| b.score.cmp(&a.score));
 		self.0.truncate(10);
 		self.0.iter().position(|v| new_score == *v)
 	}
 
-	pub fn iter(&self) -> Iter<u32> {
+	pub fn iter(&self) -> Iter<ScoreRecord> {
 		self.0.iter()
 	}
 }
+
+/// Best sprint times in milliseconds for every line goal, fastest first
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct SprintTimes(BTreeMap<u32, Vec<u64>>);
+
+impl SprintTimes {
+	pub fn best(&self, lines: u32) -> Option<u64> {
+		self.0.get(&lines).and_then(|times| times.first().copied())
+	}
+
+	pub fn push_new_time(
+		&mut self,
+		lines: u32,
+		new_time: u64,
+	) -> Option<usize> {
+		let times = self.0.entry(lines).or_default();
+		times.push(new_time);
+		times.sort_unstable();
+		times.truncate(10);
+		times.iter().position(|v| new_time == *v)
+	}
+
+	pub fn iter(&self, lines: u32) -> Iter<u64> {
+		self.0.get(&lines).map_or([].iter(), |times| times.iter())
+	}
+}
+
+/// Ultra high scores for every time limit
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct UltraScores(BTreeMap<u32, Scores>);
+
+impl UltraScores {
+	pub fn push_new_score(
+		&mut self,
+		minutes: u32,
+		new_score: ScoreRecord,
+	) -> Option<usize> {
+		self.0
+			.entry(minutes)
+			.or_insert_with(Scores::new)
+			.push_new_score(new_score)
+	}
+
+	pub fn iter(&self, minutes: u32) -> Iter<ScoreRecord> {
+		self.0
+			.get(&minutes)
+			.map_or([].iter(), |scores| scores.iter())
+	}
+}
+
+/// Best dig records for every garbage row count, fastest first
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct DigRecords(BTreeMap<u32, Vec<DigRecord>>);
+
+#[derive(
+	Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize,
+)]
+pub struct DigRecord {
+	/// Milliseconds
+	pub time: u64,
+	pub pieces: u32,
+}
+
+impl DigRecords {
+	pub fn best(&self, rows: u32) -> Option<u64> {
+		self.0
+			.get(&rows)
+			.and_then(|records| records.first())
+			.map(|record| record.time)
+	}
+
+	pub fn push_new_record(
+		&mut self,
+		rows: u32,
+		new_record: DigRecord,
+	) -> Option<usize> {
+		let records = self.0.entry(rows).or_default();
+		records.push(new_record);
+		records.sort_unstable();
+		records.truncate(10);
+		records.iter().position(|v| new_record == *v)
+	}
+
+	pub fn iter(&self, rows: u32) -> Iter<DigRecord> {
+		self.0
+			.get(&rows)
+			.map_or([].iter(), |records| records.iter())
+	}
+}
+
+/// Which leaderboard the scores screen shows
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScoresPage {
+	#[default]
+	Marathon,
+	Endless,
+	Sprint,
+	Ultra,
+	Dig,
+}
+
+impl ScoresPage {
+	const ALL: [ScoresPage; 5] = [
+		ScoresPage::Marathon,
+		ScoresPage::Endless,
+		ScoresPage::Sprint,
+		ScoresPage::Ultra,
+		ScoresPage::Dig,
+	];
+
+	pub fn change(&mut self, forward: bool) {
+		let len = Self::ALL.len();
+		let idx = Self::ALL.iter().position(|p| p == self).unwrap_or(0);
+
+		*self = if forward {
+			Self::ALL[(idx + 1) % len]
+		} else {
+			Self::ALL[(idx + len - 1) % len]
+		};
+	}
+}
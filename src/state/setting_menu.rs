@@ -1,6 +1,12 @@
 use crate::{
 	common::{Menu, VecExt},
-	global::{global_audio, global_setting, Sound},
+	global::{
+		global_audio, global_setting,
+		setting::{
+			GravityCurve, LockReset, RandomizerKind, ScoringSystem, SONIC_DROP,
+		},
+		Sound,
+	},
 };
 
 pub struct SettingMenu {
@@ -11,12 +17,34 @@ pub struct SettingMenu {
 impl SettingMenu {
 	pub fn new() -> Self {
 		Self {
-			items: vec!["PARTICLE", "MUSIC", "SOUND"].into_owned_vec(),
+			items: vec![
+				"PARTICLE",
+				"MUSIC",
+				"SOUND",
+				"NEXT",
+				"DAS",
+				"ARR",
+				"SOFT DROP",
+				"GRAVITY",
+				"RANDOMIZER",
+				"LOCK DELAY",
+				"LOCK RESET",
+				"RESET LIMIT",
+				"WIDTH",
+				"HEIGHT",
+				"MESSINESS",
+				"GARBAGE",
+				"SCORING",
+				"ARE",
+				"LINE CLEAR",
+				"SPAWN UP",
+			]
+			.into_owned_vec(),
 			cursor: 0,
 		}
 	}
 
-	pub fn handle_enter(&self) {
+	pub fn handle_change(&self, forward: bool) {
 		use setting_menu_idx::*;
 
 		let setting = global_setting();
@@ -25,6 +53,23 @@ impl SettingMenu {
 			PARTICLE => setting.switch_particle(),
 			MUSIC => setting.switch_music(),
 			SOUND => setting.switch_sound(),
+			NEXT => setting.change_next_count(forward),
+			DAS => setting.change_das(forward),
+			ARR => setting.change_arr(forward),
+			SOFT_DROP => setting.change_soft_drop(forward),
+			GRAVITY => setting.change_gravity(forward),
+			RANDOMIZER => setting.change_randomizer(forward),
+			LOCK_DELAY => setting.change_lock_delay(forward),
+			LOCK_RESET => setting.change_lock_reset(forward),
+			RESET_LIMIT => setting.change_lock_reset_limit(forward),
+			WIDTH => setting.change_board_cols(forward),
+			HEIGHT => setting.change_board_visible_rows(forward),
+			MESSINESS => setting.change_dig_messiness(forward),
+			GARBAGE => setting.change_garbage_interval(forward),
+			SCORING => setting.change_scoring(forward),
+			ARE => setting.change_entry_delay(forward),
+			LINE_CLEAR => setting.change_line_clear_delay(forward),
+			SPAWN_UP => setting.switch_spawn_up(),
 			_ => (),
 		}
 
@@ -48,14 +93,40 @@ impl Menu for SettingMenu {
 	fn items(&self) -> Vec<String> {
 		use setting_menu_idx::*;
 
-		let mut items = self.items.clone();
 		let setting = global_setting();
 
-		items[PARTICLE] = particle_text(setting.particle());
-		items[MUSIC] = music_text(setting.music());
-		items[SOUND] = sound_text(setting.sound());
-
-		items
+		self.items
+			.iter()
+			.enumerate()
+			.map(|(i, label)| {
+				let value = match i {
+					PARTICLE => bool_text(setting.particle()),
+					MUSIC => bool_text(setting.music()),
+					SOUND => bool_text(setting.sound()),
+					NEXT => setting.next_count().to_string(),
+					DAS => ms_text(setting.das()),
+					ARR => arr_text(setting.arr()),
+					SOFT_DROP => soft_drop_text(setting.soft_drop()),
+					GRAVITY => gravity_text(setting.gravity()),
+					RANDOMIZER => randomizer_text(setting.randomizer()),
+					LOCK_DELAY => ms_text(setting.lock_delay()),
+					LOCK_RESET => lock_reset_text(setting.lock_reset()),
+					RESET_LIMIT => setting.lock_reset_limit().to_string(),
+					WIDTH => setting.board_cols().to_string(),
+					HEIGHT => setting.board_visible_rows().to_string(),
+					MESSINESS => format!("{}%", setting.dig_messiness()),
+					GARBAGE => {
+						garbage_interval_text(setting.garbage_interval())
+					}
+					SCORING => scoring_text(setting.scoring()),
+					ARE => ms_text(setting.entry_delay()),
+					LINE_CLEAR => ms_text(setting.line_clear_delay()),
+					SPAWN_UP => bool_text(setting.spawn_up()),
+					_ => String::new(),
+				};
+				item_text(label, value)
+			})
+			.collect()
 	}
 }
 
@@ -63,24 +134,99 @@ mod setting_menu_idx {
 	pub const PARTICLE: usize = 0;
 	pub const MUSIC: usize = 1;
 	pub const SOUND: usize = 2;
+	pub const NEXT: usize = 3;
+	pub const DAS: usize = 4;
+	pub const ARR: usize = 5;
+	pub const SOFT_DROP: usize = 6;
+	pub const GRAVITY: usize = 7;
+	pub const RANDOMIZER: usize = 8;
+	pub const LOCK_DELAY: usize = 9;
+	pub const LOCK_RESET: usize = 10;
+	pub const RESET_LIMIT: usize = 11;
+	pub const WIDTH: usize = 12;
+	pub const HEIGHT: usize = 13;
+	pub const MESSINESS: usize = 14;
+	pub const GARBAGE: usize = 15;
+	pub const SCORING: usize = 16;
+	pub const ARE: usize = 17;
+	pub const LINE_CLEAR: usize = 18;
+	pub const SPAWN_UP: usize = 19;
+}
+
+pub fn item_text(label: &str, value: String) -> String {
+	format!("{:<12}{:>9}", format!("{}:", label), value)
 }
 
-fn bool_text(v: bool) -> &'static str {
+pub fn bool_text(v: bool) -> String {
 	if v {
-		"ON"
+		"ON".to_string()
 	} else {
-		"OFF"
+		"OFF".to_string()
 	}
 }
 
-fn particle_text(v: bool) -> String {
-	format!("{:<10}{:>3}", "PARTICLE: ", bool_text(v))
+fn ms_text(v: u16) -> String {
+	format!("{}MS", v)
+}
+
+fn arr_text(v: u16) -> String {
+	if v == 0 {
+		"INSTANT".to_string()
+	} else {
+		ms_text(v)
+	}
 }
 
-fn music_text(v: bool) -> String {
-	format!("{:<10}{:>3}", "MUSIC: ", bool_text(v))
+fn soft_drop_text(v: u8) -> String {
+	if v == SONIC_DROP {
+		"SONIC".to_string()
+	} else {
+		format!("{}X", v)
+	}
 }
 
-fn sound_text(v: bool) -> String {
-	format!("{:<10}{:>3}", "SOUND: ", bool_text(v))
+fn garbage_interval_text(v: u8) -> String {
+	if v == 0 {
+		"OFF".to_string()
+	} else {
+		format!("{}S", v)
+	}
+}
+
+fn gravity_text(v: GravityCurve) -> String {
+	match v {
+		GravityCurve::Guideline => "GUIDELINE",
+		GravityCurve::Nes => "NES",
+		GravityCurve::Tgm => "TGM",
+	}
+	.to_string()
+}
+
+fn randomizer_text(v: RandomizerKind) -> String {
+	match v {
+		RandomizerKind::SevenBag => "7-BAG",
+		RandomizerKind::FourteenBag => "14-BAG",
+		RandomizerKind::Random => "RANDOM",
+		RandomizerKind::NesReroll => "NES",
+		RandomizerKind::TgmHistory => "TGM",
+	}
+	.to_string()
+}
+
+fn scoring_text(v: ScoringSystem) -> String {
+	match v {
+		ScoringSystem::Guideline => "GUIDELINE",
+		ScoringSystem::Nes => "NES",
+		ScoringSystem::Tgm => "TGM",
+	}
+	.to_string()
+}
+
+fn lock_reset_text(v: LockReset) -> String {
+	match v {
+		LockReset::Move => "MOVE",
+		LockReset::Step => "STEP",
+		LockReset::Infinite => "INFINITE",
+	}
+	.to_string()
 }
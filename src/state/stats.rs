@@ -1,27 +1,104 @@
 use serde::{Deserialize, Serialize};
 
-use crate::common::Reset;
+use super::tetromino::TSpin;
+use crate::{common::Reset, global::setting::ScoringSystem};
 
 #[derive(Clone, Deserialize, Serialize)]
 pub struct Stats {
 	pub level: u32,
+	#[serde(default = "first_level")]
+	pub start_level: u32,
 	pub score: u32,
 	pub lines: u32,
 	pub combo: i32,
+	#[serde(default)]
+	pub t_spins: u32,
+	/// Back-to-back chain of tetrises and T-spin line clears
+	#[serde(default = "no_chain")]
+	pub b2b: i32,
+	/// Locked pieces
+	#[serde(default)]
+	pub pieces: u32,
+	/// Rows of the initial garbage still to dig through
+	#[serde(default)]
+	pub garbage_left: u32,
+	#[serde(default)]
+	pub scoring: ScoringSystem,
+	/// Cells the current piece fell by soft or hard drop
+	#[serde(default)]
+	dropped: u32,
+	/// Combo multiplier of the TGM scoring
+	#[serde(default = "no_tgm_combo")]
+	tgm_combo: u32,
+	#[serde(skip)]
+	pub last_clear: Option<ClearAction>,
+}
+
+/// What the last locked piece achieved, shown in the sidebar
+#[derive(Clone, Copy)]
+pub struct ClearAction {
+	pub lines: usize,
+	pub t_spin: TSpin,
+	pub b2b: bool,
+	pub perfect_clear: bool,
+	/// Points the clear scored
+	pub score: u32,
+}
+
+impl ClearAction {
+	pub fn text(&self) -> String {
+		let lines = match self.lines {
+			1 => "SINGLE",
+			2 => "DOUBLE",
+			3 => "TRIPLE",
+			4 => "TETRIS",
+			_ => "",
+		};
+
+		let t_spin = match self.t_spin {
+			TSpin::None => "",
+			TSpin::Mini => "MINI T-SPIN",
+			TSpin::Full => "T-SPIN",
+		};
+
+		let b2b = if self.b2b {
+			"B2B"
+		} else {
+			""
+		};
+
+		format!("{} {} {}", b2b, t_spin, lines)
+			.split_whitespace()
+			.collect::<Vec<_>>()
+			.join(" ")
+	}
 }
 
 impl Stats {
 	pub fn new() -> Self {
 		Self {
 			level: 1,
+			start_level: 1,
 			score: 0,
 			lines: 0,
 			combo: -1,
+			t_spins: 0,
+			b2b: -1,
+			pieces: 0,
+			garbage_left: 0,
+			scoring: ScoringSystem::default(),
+			dropped: 0,
+			tgm_combo: 1,
+			last_clear: None,
 		}
 	}
 
-	pub fn update(&mut self, rows_len: usize) {
+	pub fn update(&mut self, rows_len: usize, t_spin: TSpin) {
 		let previous_level = self.level;
+		// The Arcade version levels up with every piece and every line
+		let tgm_level = self.pieces + self.lines;
+
+		self.pieces += 1;
 
 		if rows_len > 0 {
 			self.lines += rows_len as u32;
@@ -32,22 +109,202 @@ impl Stats {
 				self.level = new_level;
 			}
 
-			let base_score = match rows_len {
-				1 => 100,
-				2 => 300,
-				3 => 500,
-				4 => 800,
-				_ => 0,
-			};
-			self.score += base_score * self.level;
 			self.combo += 1;
 		} else {
 			self.combo = -1;
 		}
+
+		let difficult =
+			rows_len == 4 || (rows_len > 0 && t_spin != TSpin::None);
+
+		if difficult {
+			self.b2b += 1;
+		} else if rows_len > 0 {
+			self.b2b = -1;
+		}
+
+		let b2b = difficult && self.b2b > 0;
+
+		let score = match self.scoring {
+			ScoringSystem::Guideline => {
+				self.guideline_score(rows_len, t_spin, b2b)
+			}
+			ScoringSystem::Nes => nes_score(rows_len, previous_level),
+			ScoringSystem::Tgm => self.tgm_score(rows_len, tgm_level),
+		};
+		self.score += score;
+		self.dropped = 0;
+
+		if t_spin != TSpin::None {
+			self.t_spins += 1;
+		}
+
+		self.last_clear = if rows_len > 0 || t_spin != TSpin::None {
+			Some(ClearAction {
+				lines: rows_len,
+				t_spin,
+				b2b,
+				perfect_clear: false,
+				score,
+			})
+		} else {
+			None
+		};
+	}
+
+	/// Points for cells fallen by soft drop
+	pub fn soft_drop(&mut self, cells: u32) {
+		self.dropped += cells;
+		if self.scoring != ScoringSystem::Tgm {
+			self.score += cells;
+		}
+	}
+
+	/// Points for cells fallen by hard drop
+	pub fn hard_drop(&mut self, cells: u32) {
+		self.dropped += cells;
+		if self.scoring == ScoringSystem::Guideline {
+			self.score += cells * 2;
+		}
+	}
+
+	fn guideline_score(
+		&self,
+		rows_len: usize,
+		t_spin: TSpin,
+		b2b: bool,
+	) -> u32 {
+		let base_score = match (t_spin, rows_len) {
+			(TSpin::None, 1) => 100,
+			(TSpin::None, 2) => 300,
+			(TSpin::None, 3) => 500,
+			(TSpin::None, 4) => 800,
+			(TSpin::Mini, 0) => 100,
+			(TSpin::Mini, 1) => 200,
+			(TSpin::Mini, 2) => 400,
+			(TSpin::Full, 0) => 400,
+			(TSpin::Full, 1) => 800,
+			(TSpin::Full, 2) => 1200,
+			(TSpin::Full, 3) => 1600,
+			_ => 0,
+		};
+		let mut score = if b2b {
+			base_score * self.level * 3 / 2
+		} else {
+			base_score * self.level
+		};
+
 		if self.combo > 0 {
-			self.score += 50 * self.combo as u32 * self.level;
+			score += 50 * self.combo as u32 * self.level;
+		}
+
+		score
+	}
+
+	/// `(ceil((level + lines) / 4) + dropped) * lines * combo`, the perfect
+	/// clear bonus is added once the line clear is finished
+	fn tgm_score(&mut self, rows_len: usize, level: u32) -> u32 {
+		if rows_len == 0 {
+			self.tgm_combo = 1;
+			return 0;
 		}
+
+		let lines = rows_len as u32;
+
+		self.tgm_combo += 2 * lines - 2;
+
+		((level + lines).div_ceil(4) + self.dropped) * lines * self.tgm_combo
 	}
+
+	/// Grade the TGM score has reached
+	pub fn grade(&self) -> Option<&'static str> {
+		if self.scoring != ScoringSystem::Tgm {
+			return None;
+		}
+
+		TGM_GRADES
+			.iter()
+			.rev()
+			.find(|(score, _)| self.score >= *score)
+			.map(|(_, grade)| *grade)
+	}
+
+	pub fn set_start_level(&mut self, level: u32) {
+		self.level = level;
+		self.start_level = level;
+	}
+
+	/// Called once the line clear is finished and the board is empty
+	pub fn perfect_clear(&mut self) {
+		let Some(action) = &mut self.last_clear else {
+			return;
+		};
+
+		match self.scoring {
+			ScoringSystem::Guideline => {
+				let base_score = match action.lines {
+					1 => 800,
+					2 => 1200,
+					3 => 1800,
+					4 if action.b2b => 3200,
+					4 => 2000,
+					_ => 0,
+				};
+				self.score += base_score * self.level;
+			}
+			ScoringSystem::Nes => (),
+			// The clear scores four times
+			ScoringSystem::Tgm => self.score += action.score * 3,
+		}
+		action.perfect_clear = true;
+	}
+}
+
+/// Points of a clear on level 0 of the NES version, which is level 1 here
+fn nes_score(rows_len: usize, level: u32) -> u32 {
+	let base_score = match rows_len {
+		1 => 40,
+		2 => 100,
+		3 => 300,
+		4 => 1200,
+		_ => 0,
+	};
+
+	base_score * level
+}
+
+/// Lowest score of every grade of the Arcade version
+const TGM_GRADES: [(u32, &str); 18] = [
+	(0, "9"),
+	(400, "8"),
+	(800, "7"),
+	(1400, "6"),
+	(2000, "5"),
+	(3500, "4"),
+	(5500, "3"),
+	(8000, "2"),
+	(12000, "1"),
+	(16000, "S1"),
+	(22000, "S2"),
+	(30000, "S3"),
+	(40000, "S4"),
+	(52000, "S5"),
+	(66000, "S6"),
+	(82000, "S7"),
+	(100000, "S8"),
+	(120000, "S9"),
+];
+
+fn no_tgm_combo() -> u32 {
+	1
+}
+
+fn no_chain() -> i32 {
+	-1
+}
+
+fn first_level() -> u32 {
+	1
 }
 
 impl Reset for Stats {
@@ -0,0 +1,66 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{common::Reset, global::is_paused};
+
+/// Game time, the time spent paused is not counted
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct Stopwatch {
+	elapsed: Duration,
+	#[serde(skip)]
+	last_instant: Option<Instant>,
+	/// Game time when the stopwatch was stopped
+	#[serde(skip)]
+	stopped_at: Option<Duration>,
+}
+
+impl Stopwatch {
+	/// Called on every tick and right before pausing or resuming, the time
+	/// since the last call counts only when the game was running
+	pub fn update(&mut self) {
+		let now = Instant::now();
+
+		if let Some(last) = self.last_instant {
+			if !is_paused() {
+				self.elapsed += now - last;
+			}
+		}
+
+		self.last_instant = Some(now);
+	}
+
+	/// Freeze the time of the game, the clock keeps running
+	pub fn stop(&mut self) {
+		self.update();
+		self.stopped_at.get_or_insert(self.elapsed);
+	}
+
+	pub fn elapsed(&self) -> Duration {
+		self.stopped_at.unwrap_or(self.elapsed)
+	}
+
+	/// Game time that keeps running after the stop, delays are timed by it
+	/// so that they still run out once the goal is reached
+	pub fn clock(&self) -> Duration {
+		self.elapsed
+	}
+}
+
+impl Reset for Stopwatch {
+	fn reset(&mut self) {
+		*self = Self::default();
+	}
+}
+
+/// `M:SS.mmm`
+pub fn format_time(time: Duration) -> String {
+	let millis = time.as_millis();
+
+	format!(
+		"{}:{:02}.{:03}",
+		millis / 60_000,
+		millis / 1000 % 60,
+		millis % 1000
+	)
+}
@@ -2,8 +2,9 @@ use serde::{Deserialize, Serialize};
 
 use super::{MainBoard, SharedMainBoard};
 use crate::{
-	common::{pos, Position, TetrominoKind},
+	common::{pos, Point, Position, TetrominoKind},
 	consts::MAIN_BOARD_BUFFER_ROWS,
+	global::board_cols,
 };
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -11,6 +12,14 @@ pub struct Tetromino {
 	pub kind: TetrominoKind,
 	pub position: Position,
 	orientation: Orientation,
+	/// SRS test number of the last successful rotation, cleared by any
+	/// successful movement. Half turns always count as test 1 since the
+	/// last-kick upgrade only applies to quarter turns
+	#[serde(default)]
+	last_kick: Option<u8>,
+	/// Lowest row the bottom of the piece has reached
+	#[serde(skip)]
+	lowest_row: i8,
 	#[serde(skip)]
 	pub blink: bool,
 	#[serde(skip, default = "MainBoard::new_shared")]
@@ -24,6 +33,8 @@ impl Tetromino {
 			position: Position::default(),
 			blink: false,
 			orientation: Orientation::default(),
+			last_kick: None,
+			lowest_row: i8::MIN,
 			board,
 		}
 	}
@@ -33,10 +44,17 @@ impl Tetromino {
 		self.orientation = Orientation::default();
 		self.position = kind.init_position(self.orientation.into());
 		self.position.update(|p| {
-			p.x += 3;
+			p.x += (board_cols() as i8 - 4) / 2;
 			p.y += MAIN_BOARD_BUFFER_ROWS as i8;
 		});
 		self.blink = false;
+		self.last_kick = None;
+		self.lowest_row = i8::MIN;
+	}
+
+	/// One row up, for spawning above a blocked spawn position
+	pub fn raise(&mut self) {
+		self.position.update(|p| p.y -= 1);
 	}
 
 	pub fn set_board(&mut self, board: SharedMainBoard) {
@@ -78,16 +96,50 @@ impl Tetromino {
 			false
 		} else {
 			self.position = position;
+			self.last_kick = None;
 			true
 		}
 	}
 
+	/// Falls up to `rows` cells, returns how many it actually fell
+	pub fn fall(&mut self, rows: u8) -> u8 {
+		let mut fallen = 0;
+
+		while fallen < rows && self.walk(TetrominoAction::SoftDrop) {
+			fallen += 1;
+		}
+
+		fallen
+	}
+
+	pub fn drop_to(&mut self, position: &Position) {
+		if self.position != *position {
+			self.position.clone_from(position);
+			self.last_kick = None;
+		}
+	}
+
+	/// Whether the piece has gone below every row it reached before, the
+	/// first call after spawning is always true
+	pub fn reach_lowest_row(&mut self) -> bool {
+		let row = self.position.bottom_point().y;
+
+		if row > self.lowest_row {
+			self.lowest_row = row;
+			true
+		} else {
+			false
+		}
+	}
+
 	pub fn rotate(&mut self, action: TetrominoAction) -> bool {
 		use Orientation::*;
 
 		let init_position = self.kind.init_position(self.orientation.into());
 		let diff = self.position.clone() - init_position;
 
+		let half_turn = matches!(action, TetrominoAction::Rotate180);
+
 		let next_orientation = match action {
 			TetrominoAction::RotateRight => {
 				match self.orientation {
@@ -105,6 +157,14 @@ impl Tetromino {
 					E => N,
 				}
 			}
+			TetrominoAction::Rotate180 => {
+				match self.orientation {
+					N => S,
+					S => N,
+					E => W,
+					W => E,
+				}
+			}
 			_ => unreachable!(),
 		};
 
@@ -116,67 +176,20 @@ impl Tetromino {
 		if rotate_position.is_outside_the_board()
 			|| self.board.borrow().is_collision(&rotate_position)
 		{
-			let kick_offest = match (&self.orientation, next_orientation) {
-				(N, E) => {
-					if self.kind == TetrominoKind::I {
-						kick_map_i::NE
-					} else {
-						kick_map_jlstz::NE
-					}
-				}
-				(E, N) => {
-					if self.kind == TetrominoKind::I {
-						kick_map_i::EN
-					} else {
-						kick_map_jlstz::EN
-					}
-				}
-				(E, S) => {
-					if self.kind == TetrominoKind::I {
-						kick_map_i::ES
-					} else {
-						kick_map_jlstz::ES
-					}
-				}
-				(S, E) => {
-					if self.kind == TetrominoKind::I {
-						kick_map_i::SE
-					} else {
-						kick_map_jlstz::SE
-					}
-				}
-				(S, W) => {
-					if self.kind == TetrominoKind::I {
-						kick_map_i::SW
-					} else {
-						kick_map_jlstz::SW
-					}
-				}
-				(W, S) => {
-					if self.kind == TetrominoKind::I {
-						kick_map_i::WS
-					} else {
-						kick_map_jlstz::WS
+			let kick_offest: Vec<Point<i8>> =
+				match (&self.orientation, next_orientation) {
+					(N, S) => kick_map_180::NS.map(Point::new).to_vec(),
+					(S, N) => kick_map_180::SN.map(Point::new).to_vec(),
+					(E, W) => kick_map_180::EW.map(Point::new).to_vec(),
+					(W, E) => kick_map_180::WE.map(Point::new).to_vec(),
+					(from, to) => {
+						self.quarter_kick_offest(*from, to)
+							.into_iter()
+							.collect()
 					}
-				}
-				(W, N) => {
-					if self.kind == TetrominoKind::I {
-						kick_map_i::WN
-					} else {
-						kick_map_jlstz::WN
-					}
-				}
-				(N, W) => {
-					if self.kind == TetrominoKind::I {
-						kick_map_i::NW
-					} else {
-						kick_map_jlstz::NW
-					}
-				}
-				_ => unreachable!(),
-			};
+				};
 
-			for offest in kick_offest.into_iter() {
+			for (i, offest) in kick_offest.into_iter().enumerate() {
 				let kick_position = rotate_position.clone() + offest;
 
 				if kick_position.is_outside_the_board()
@@ -188,6 +201,11 @@ impl Tetromino {
 				if self.position != kick_position {
 					self.position = kick_position;
 					self.orientation = next_orientation;
+					self.last_kick = if half_turn {
+						Some(1)
+					} else {
+						Some(i as u8 + 2)
+					};
 					rotated = true;
 				}
 
@@ -196,12 +214,123 @@ impl Tetromino {
 		} else if self.position != rotate_position {
 			self.position = rotate_position;
 			self.orientation = next_orientation;
+			self.last_kick = Some(1);
 			rotated = true;
 		}
 
 		rotated
 	}
 
+	fn quarter_kick_offest(
+		&self,
+		from: Orientation,
+		to: Orientation,
+	) -> Position {
+		use Orientation::*;
+
+		match (from, to) {
+			(N, E) => {
+				if self.kind == TetrominoKind::I {
+					kick_map_i::NE
+				} else {
+					kick_map_jlstz::NE
+				}
+			}
+			(E, N) => {
+				if self.kind == TetrominoKind::I {
+					kick_map_i::EN
+				} else {
+					kick_map_jlstz::EN
+				}
+			}
+			(E, S) => {
+				if self.kind == TetrominoKind::I {
+					kick_map_i::ES
+				} else {
+					kick_map_jlstz::ES
+				}
+			}
+			(S, E) => {
+				if self.kind == TetrominoKind::I {
+					kick_map_i::SE
+				} else {
+					kick_map_jlstz::SE
+				}
+			}
+			(S, W) => {
+				if self.kind == TetrominoKind::I {
+					kick_map_i::SW
+				} else {
+					kick_map_jlstz::SW
+				}
+			}
+			(W, S) => {
+				if self.kind == TetrominoKind::I {
+					kick_map_i::WS
+				} else {
+					kick_map_jlstz::WS
+				}
+			}
+			(W, N) => {
+				if self.kind == TetrominoKind::I {
+					kick_map_i::WN
+				} else {
+					kick_map_jlstz::WN
+				}
+			}
+			(N, W) => {
+				if self.kind == TetrominoKind::I {
+					kick_map_i::NW
+				} else {
+					kick_map_jlstz::NW
+				}
+			}
+			_ => unreachable!(),
+		}
+	}
+
+	/// Three-corner T-spin check, a mini T-spin is upgraded when the
+	/// rotation used the last kick test
+	pub fn t_spin(&self) -> TSpin {
+		use Orientation::*;
+
+		let Some(kick) = self.last_kick else {
+			return TSpin::None;
+		};
+
+		if self.kind != TetrominoKind::T {
+			return TSpin::None;
+		}
+
+		let offset = (self.position.clone()
+			- self.kind.init_position(self.orientation.into()))
+		.into_iter()
+		.next()
+		.unwrap();
+		let (cx, cy) = (offset.x + 1, offset.y + 1);
+
+		let board = self.board.borrow();
+		let corners = [(-1, -1), (1, -1), (1, 1), (-1, 1)]
+			.map(|(dx, dy)| board.is_blocked(cx + dx, cy + dy));
+
+		if corners.iter().filter(|c| **c).count() < 3 {
+			return TSpin::None;
+		}
+
+		let (a, b) = match self.orientation {
+			N => (0, 1),
+			E => (1, 2),
+			S => (2, 3),
+			W => (3, 0),
+		};
+
+		if (corners[a] && corners[b]) || kick == 5 {
+			TSpin::Full
+		} else {
+			TSpin::Mini
+		}
+	}
+
 	pub fn hidden(&mut self) {
 		self.position = Position::default();
 	}
@@ -227,12 +356,21 @@ impl From<Orientation> for usize {
 	}
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum TSpin {
+	#[default]
+	None,
+	Mini,
+	Full,
+}
+
 pub enum TetrominoAction {
 	WalkLeft,
 	WalkRight,
 	SoftDrop,
 	RotateRight,
 	RotateLeft,
+	Rotate180,
 }
 
 mod kick_map_jlstz {
@@ -274,3 +412,14 @@ mod kick_map_i {
 
 	pub const NW: Position = pos([(-1, 0), (2, 0), (-1, 2), (2, -1)]);
 }
+
+/// SRS+ half turn kicks, the I piece uses the same table
+mod kick_map_180 {
+	pub const NS: [(i8, i8); 5] = [(0, 1), (1, 1), (-1, 1), (1, 0), (-1, 0)];
+
+	pub const SN: [(i8, i8); 5] = [(0, -1), (-1, -1), (1, -1), (-1, 0), (1, 0)];
+
+	pub const EW: [(i8, i8); 5] = [(1, 0), (1, 2), (1, 1), (0, 2), (0, 1)];
+
+	pub const WE: [(i8, i8); 5] = [(-1, 0), (-1, 2), (-1, 1), (0, 2), (0, 1)];
+}
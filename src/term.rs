@@ -5,15 +5,22 @@ use std::{
 
 use anyhow::Result;
 use crossterm::{
+	event::{
+		KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+		PushKeyboardEnhancementFlags,
+	},
 	execute,
 	terminal::{
-		disable_raw_mode, enable_raw_mode, EnterAlternateScreen,
-		LeaveAlternateScreen, SetTitle,
+		disable_raw_mode, enable_raw_mode, supports_keyboard_enhancement,
+		EnterAlternateScreen, LeaveAlternateScreen, SetTitle,
 	},
 };
 use ratatui::{backend::CrosstermBackend, Frame, Terminal};
 
-use crate::consts::APP_NAME;
+use crate::{
+	consts::APP_NAME,
+	global::{is_key_release_supported, set_key_release_supported},
+};
 
 pub struct Term {
 	terminal: Terminal<CrosstermBackend<Stderr>>,
@@ -32,6 +39,20 @@ impl Term {
 		enable_raw_mode()?;
 		execute!(stderr(), SetTitle(APP_NAME), EnterAlternateScreen)?;
 
+		// Windows always reports key releases, other platforms need the
+		// keyboard enhancement protocol
+		if cfg!(windows) {
+			set_key_release_supported(true);
+		} else if supports_keyboard_enhancement().unwrap_or(false) {
+			execute!(
+				stderr(),
+				PushKeyboardEnhancementFlags(
+					KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+				)
+			)?;
+			set_key_release_supported(true);
+		}
+
 		let panic_hook = panic::take_hook();
 		panic::set_hook(Box::new(move |panic| {
 			reset().expect("failed to reset the terminal");
@@ -59,6 +80,9 @@ impl Term {
 }
 
 fn reset() -> Result<()> {
+	if !cfg!(windows) && is_key_release_supported() {
+		execute!(stderr(), PopKeyboardEnhancementFlags)?;
+	}
 	disable_raw_mode()?;
 	execute!(stderr(), LeaveAlternateScreen)?;
 
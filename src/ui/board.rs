@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use ratatui::{
 	layout::{Constraint, Layout, Rect},
 	Frame,
@@ -8,10 +10,9 @@ use super::cell::{
 };
 use crate::{
 	common::Board,
-	consts::{
-		MAIN_BOARD_BUFFER_ROWS, MAIN_BOARD_COLS, MAIN_BOARD_VISIBLE_ROWS,
-	},
-	state::State,
+	consts::MAIN_BOARD_BUFFER_ROWS,
+	global::{board_cols, board_visible_rows, setting::StackVisibility},
+	state::{main_board::MainBoard, State},
 };
 
 pub fn main_board(
@@ -22,32 +23,54 @@ pub fn main_board(
 	cell_width: u16,
 ) {
 	let mut board = state.board.borrow_mut();
+	let modifiers = state.modifiers;
+	let stack = if state.is_stack_revealed() {
+		StackVisibility::Normal
+	} else {
+		modifiers.stack
+	};
+	// The ghost would give away the surface of a hidden stack
+	let show_ghost = !modifiers.no_ghost && stack == StackVisibility::Normal;
+	let now = state.stopwatch.elapsed();
 
-	let v_chunks = Layout::vertical(
-		[Constraint::Length(cell_height); MAIN_BOARD_VISIBLE_ROWS],
-	)
-	.areas::<MAIN_BOARD_VISIBLE_ROWS>(rect);
+	let v_chunks = Layout::vertical(vec![
+		Constraint::Length(cell_height);
+		board_visible_rows()
+	])
+	.split(rect);
 
-	for (y, v_area) in v_chunks.into_iter().enumerate() {
-		let h_chunks = Layout::horizontal(
-			[Constraint::Length(cell_width); MAIN_BOARD_COLS],
-		)
-		.areas::<MAIN_BOARD_COLS>(v_area);
+	for (y, v_area) in v_chunks.iter().copied().enumerate() {
+		let h_chunks = Layout::horizontal(vec![
+			Constraint::Length(cell_width);
+			board_cols()
+		])
+		.split(v_area);
 
 		let y = y + MAIN_BOARD_BUFFER_ROWS;
 
-		for (x, h_area) in h_chunks.into_iter().enumerate() {
+		for (x, h_area) in h_chunks.iter().copied().enumerate() {
 			if state.alive_tetromino.position.contains(x, y) {
-				let kind = &state.alive_tetromino.kind;
+				let kind = modifiers.shown_kind(&state.alive_tetromino.kind);
 				if state.alive_tetromino.blink {
 					dark_tetromino_cell(f, h_area, kind);
 				} else {
 					tetromino_cell(f, h_area, kind);
 				}
-			} else if state.ghost_tetromino.position.contains(x, y) {
-				ghost_cell(f, h_area, &state.ghost_tetromino.kind);
+			} else if show_ghost
+				&& state.ghost_tetromino.position.contains(x, y)
+			{
+				ghost_cell(
+					f,
+					h_area,
+					modifiers.shown_kind(&state.ghost_tetromino.kind),
+				);
 			} else if let Some(kind) = board.get_kind(x, y) {
-				tetromino_cell(f, h_area, kind);
+				let kind = modifiers.shown_kind(kind);
+				match stack_cell(&board, stack, now, x, y) {
+					StackCell::Shown => tetromino_cell(f, h_area, kind),
+					StackCell::Fading => dark_tetromino_cell(f, h_area, kind),
+					StackCell::Hidden => empty_cell(f, h_area),
+				}
 			} else {
 				empty_cell(f, h_area);
 			}
@@ -61,3 +84,37 @@ pub fn main_board(
 		}
 	}
 }
+
+enum StackCell {
+	Shown,
+	Fading,
+	Hidden,
+}
+
+/// A fading cell turns dark after [`FADE_START`] and is gone after
+/// [`FADE_END`]
+fn stack_cell(
+	board: &MainBoard,
+	stack: StackVisibility,
+	now: Duration,
+	x: usize,
+	y: usize,
+) -> StackCell {
+	match stack {
+		StackVisibility::Normal => StackCell::Shown,
+		StackVisibility::Fading => {
+			let age = now.saturating_sub(board.lock_time(x, y));
+			if age < FADE_START {
+				StackCell::Shown
+			} else if age < FADE_END {
+				StackCell::Fading
+			} else {
+				StackCell::Hidden
+			}
+		}
+		StackVisibility::Invisible => StackCell::Hidden,
+	}
+}
+
+const FADE_START: Duration = Duration::from_secs(3);
+const FADE_END: Duration = Duration::from_secs(5);
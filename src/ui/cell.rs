@@ -26,6 +26,10 @@ pub fn dark_tetromino_cell(f: &mut Frame, rect: Rect, kind: &TetrominoKind) {
 	cell(f, rect, kind.dark_color());
 }
 
+pub fn disabled_cell(f: &mut Frame, rect: Rect) {
+	cell(f, rect, Color::Rgb(105, 105, 105));
+}
+
 pub fn ghost_cell(f: &mut Frame, rect: Rect, kind: &TetrominoKind) {
 	let block = Block::bordered()
 		.border_type(BorderType::Rounded)
@@ -54,5 +58,6 @@ fn ghost_style(kind: &TetrominoKind) -> Style {
 		TetrominoKind::J => Style::new().light_blue(),
 		TetrominoKind::S => Style::new().green(),
 		TetrominoKind::Z => Style::new().light_red(),
+		TetrominoKind::Garbage => Style::new().gray(),
 	}
 }
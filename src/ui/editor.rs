@@ -0,0 +1,192 @@
+use ratatui::{
+	layout::{Constraint, Flex, Layout, Rect},
+	style::{Color, Style, Stylize},
+	text::{Line, Span, Text},
+	widgets::{Block, Clear, Paragraph, Wrap},
+	Frame,
+};
+
+use super::{
+	calc_cell_size,
+	cell::{dark_tetromino_cell, empty_cell, ghost_cell, tetromino_cell},
+	utils::rounded_block,
+	SIDEBAR_COLS,
+};
+use crate::{
+	common::TetrominoKind,
+	state::{
+		editor::Editor,
+		puzzle::{kind_to_char, PUZZLE_FREE_ROWS},
+	},
+};
+
+pub fn editor(f: &mut Frame, screen: Rect, editor: &Editor) {
+	f.render_widget(Clear, screen);
+	f.render_widget(Block::new().on_black(), screen);
+
+	let rows = editor.rows() as u16;
+	let cols = editor.cols() as u16;
+
+	// Cells are as large as in a game on the board with the free rows
+	let (cell_height, cell_width) =
+		calc_cell_size(screen, rows + PUZZLE_FREE_ROWS as u16, cols);
+
+	let vertical_area =
+		Layout::vertical([Constraint::Length(cell_height * rows)])
+			.flex(Flex::Center)
+			.areas::<1>(screen)[0];
+
+	let [board_area, info_area] = Layout::horizontal([
+		Constraint::Length(cell_width * cols),
+		Constraint::Length(cell_width * SIDEBAR_COLS),
+	])
+	.flex(Flex::Center)
+	.areas::<2>(vertical_area);
+
+	board(f, board_area, editor, cell_height, cell_width);
+	info(f, info_area, editor);
+}
+
+fn board(
+	f: &mut Frame,
+	rect: Rect,
+	editor: &Editor,
+	cell_height: u16,
+	cell_width: u16,
+) {
+	let v_chunks =
+		Layout::vertical(vec![Constraint::Length(cell_height); editor.rows()])
+			.split(rect);
+
+	for (y, v_area) in v_chunks.iter().copied().enumerate() {
+		let h_chunks = Layout::horizontal(vec![
+			Constraint::Length(cell_width);
+			editor.cols()
+		])
+		.split(v_area);
+
+		for (x, h_area) in h_chunks.iter().copied().enumerate() {
+			let cell = &editor.cells[y][x];
+
+			if (x, y) == (editor.cursor_x, editor.cursor_y) {
+				match cell {
+					Some(kind) => dark_tetromino_cell(f, h_area, kind),
+					None => {
+						ghost_cell(
+							f,
+							h_area,
+							&editor.brush().unwrap_or(TetrominoKind::Garbage),
+						)
+					}
+				}
+			} else {
+				match cell {
+					Some(kind) => tetromino_cell(f, h_area, kind),
+					None => empty_cell(f, h_area),
+				}
+			}
+		}
+	}
+}
+
+fn info(f: &mut Frame, rect: Rect, editor: &Editor) {
+	let block = rounded_block()
+		.title("BOARD EDITOR")
+		.border_style(Style::new().fg(Color::DarkGray));
+	let area = block.inner(rect);
+
+	f.render_widget(block, rect);
+
+	let label = |text: &'static str| Line::styled(text, Style::new().blue());
+
+	let brush = match editor.brush() {
+		Some(TetrominoKind::Garbage) => {
+			Line::styled(
+				"GARBAGE",
+				Style::new().fg(TetrominoKind::Garbage.color()),
+			)
+		}
+		Some(kind) => {
+			Line::styled(
+				kind_to_char(&kind).to_string(),
+				Style::new().fg(kind.color()),
+			)
+		}
+		None => Line::styled("ERASER", Style::new().white()),
+	};
+
+	let pieces = if editor.pieces.is_empty() {
+		Line::styled("RANDOM", Style::new().dark_gray())
+	} else {
+		Line::from(
+			editor
+				.pieces
+				.iter()
+				.map(|kind| {
+					Span::styled(
+						kind_to_char(kind).to_string(),
+						Style::new().fg(kind.color()),
+					)
+				})
+				.collect::<Vec<_>>(),
+		)
+	};
+
+	let file = match &editor.file {
+		Some(file) => {
+			Line::styled(
+				file.file_name()
+					.map(|name| name.to_string_lossy().to_string())
+					.unwrap_or_default(),
+				Style::new().white(),
+			)
+		}
+		None => Line::styled("NOT SAVED", Style::new().dark_gray()),
+	};
+
+	let mut lines = vec![
+		Line::raw(""),
+		label("BRUSH"),
+		brush,
+		Line::raw(""),
+		label("PIECES"),
+		pieces,
+		Line::raw(""),
+		label("FILE"),
+		file,
+		Line::raw(""),
+		Line::styled(
+			editor.message.clone().unwrap_or_default(),
+			Style::new().light_yellow(),
+		),
+		Line::raw(""),
+	];
+
+	lines.extend(KEYS_TEXT.iter().map(|t| {
+		Line::styled(
+			format!("{:<8}{:<12}", t[0], t[1]),
+			Style::new().dark_gray(),
+		)
+	}));
+
+	let p = Paragraph::new(Text::from(lines))
+		.centered()
+		.bold()
+		.wrap(Wrap {
+			trim: true,
+		});
+
+	f.render_widget(p, area);
+}
+
+const KEYS_TEXT: [[&str; 2]; 9] = [
+	["ARROWS", "MOVE"],
+	["SPACE", "PAINT"],
+	["C / Z", "BRUSH"],
+	["A", "ADD PIECE"],
+	["U", "REMOVE PIECE"],
+	["S", "SAVE"],
+	["O", "LOAD NEXT"],
+	["ENTER", "PLAY"],
+	["ESC", "BACK"],
+];
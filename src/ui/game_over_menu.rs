@@ -1,7 +1,8 @@
 use ratatui::{
 	layout::{Constraint, Flex, Layout},
-	style::{Color, Style},
-	text::Line,
+	style::{Color, Style, Stylize},
+	text::{Line, Text},
+	widgets::Paragraph,
 	Frame,
 };
 use tui_big_text::{BigText, PixelSize};
@@ -12,7 +13,7 @@ use super::{
 };
 use crate::state::game_over_menu::GameOverMenu;
 
-pub fn game_over_menu(f: &mut Frame, game_over_menu: &GameOverMenu) {
+pub fn game_over_menu(f: &mut Frame, game_over_menu: &GameOverMenu, seed: u64) {
 	let new_score = game_over_menu.new_score.clone();
 
 	let (width_offest, height_offest) = if new_score.is_some() {
@@ -21,9 +22,9 @@ pub fn game_over_menu(f: &mut Frame, game_over_menu: &GameOverMenu) {
 		(0, 0)
 	};
 
-	let popup = Popup::new(48 + width_offest, 26 + height_offest).render(f);
+	let popup = Popup::new(48 + width_offest, 35 + height_offest).render(f);
 
-	let mut constraints = vec![Constraint::Length(4), Constraint::Length(16)];
+	let mut constraints = vec![Constraint::Length(7), Constraint::Length(22)];
 
 	if new_score.is_some() {
 		constraints.insert(1, Constraint::Length(6));
@@ -32,7 +33,8 @@ pub fn game_over_menu(f: &mut Frame, game_over_menu: &GameOverMenu) {
 	let chunk = Layout::vertical(constraints).spacing(3).split(popup);
 
 	if let Some(score) = new_score {
-		let new_score_block = rounded_block().title("NEW SCORE");
+		let new_score_block =
+			rounded_block().title(game_over_menu.new_score_title);
 		let new_score_block_inner = new_score_block.inner(chunk[1]);
 		let score = BigText::builder()
 			.pixel_size(PixelSize::Quadrant)
@@ -47,17 +49,41 @@ pub fn game_over_menu(f: &mut Frame, game_over_menu: &GameOverMenu) {
 
 	let title = BigText::builder()
 		.pixel_size(PixelSize::Quadrant)
-		.lines([Line::raw("GAME OVER")])
-		.style(Style::new().fg(Color::Red))
+		.lines([Line::raw(game_over_menu.title)])
+		.style(Style::new().fg(
+			if matches!(game_over_menu.title, "GAME OVER" | "FAILED") {
+				Color::Red
+			} else {
+				Color::Green
+			},
+		))
 		.build()
 		.unwrap();
 
-	let title_area = Layout::horizontal([Constraint::Length(36)])
-		.flex(Flex::Center)
-		.areas::<1>(chunk[0])[0];
+	let [title_area, info_area] =
+		Layout::vertical([Constraint::Length(4), Constraint::Length(2)])
+			.spacing(1)
+			.areas::<2>(chunk[0]);
+
+	let title_area = Layout::horizontal([Constraint::Length(
+		game_over_menu.title.len() as u16 * 4,
+	)])
+	.flex(Flex::Center)
+	.areas::<1>(title_area)[0];
 
 	f.render_widget(title, title_area);
 
+	let info = Paragraph::new(Text::from(vec![
+		Line::styled(
+			game_over_menu.detail.clone().unwrap_or_default(),
+			Style::new().white().bold(),
+		),
+		Line::styled(format!("SEED: {}", seed), Style::new().dark_gray()),
+	]))
+	.centered();
+
+	f.render_widget(info, info_area);
+
 	menu(
 		f,
 		if chunk.len() == 3 {
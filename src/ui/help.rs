@@ -8,7 +8,7 @@ use ratatui::{
 use super::utils::Popup;
 
 pub fn help(f: &mut Frame) {
-	let popup = Popup::new(36, 13)
+	let popup = Popup::new(36, 17)
 		.title("HELP")
 		.padding(Padding::vertical(2))
 		.render(f);
@@ -25,12 +25,16 @@ pub fn help(f: &mut Frame) {
 	f.render_widget(p, popup);
 }
 
-const HELP_TEXT: [[&str; 2]; 7] = [
+const HELP_TEXT: [[&str; 2]; 11] = [
 	["MOVE RIGHT", "RIGHT ARROW / L"],
 	["MOVE LEFT", "LEFT ARROW / J"],
 	["ROTATA RIGHT", "UP ARROW / I"],
 	["ROTATE LEFT", "Z"],
+	["ROTATE 180", "A"],
 	["SOFT DROP", "DOWN ARROW / K"],
 	["HARD DROP", "SPACE"],
+	["HOLD", "C"],
 	["PAUSE", "ESC / P"],
+	["UNDO (PRACTICE)", "U"],
+	["REDO (PRACTICE)", "R"],
 ];
@@ -0,0 +1,41 @@
+use ratatui::{
+	layout::{Constraint, Layout, Rect},
+	Frame,
+};
+
+use super::cell::{disabled_cell, tetromino_cell};
+use crate::{
+	common::Board,
+	consts::{HOLD_BOARD_COLS, HOLD_BOARD_ROWS},
+	state::{hold_board::HoldBoard, modifiers::Modifiers},
+};
+
+pub fn hold_board(
+	f: &mut Frame,
+	rect: Rect,
+	hold_board: &HoldBoard,
+	cell_height: u16,
+	cell_width: u16,
+	modifiers: &Modifiers,
+) {
+	let v_chunks =
+		Layout::vertical([Constraint::Length(cell_height); HOLD_BOARD_ROWS])
+			.areas::<HOLD_BOARD_ROWS>(rect);
+
+	for (y, v_area) in v_chunks.into_iter().enumerate() {
+		let h_chunks = Layout::horizontal(
+			[Constraint::Length(cell_width); HOLD_BOARD_COLS],
+		)
+		.areas::<HOLD_BOARD_COLS>(v_area);
+
+		for (x, h_area) in h_chunks.into_iter().enumerate() {
+			if let Some(kind) = hold_board.get_kind(x, y) {
+				if hold_board.used {
+					disabled_cell(f, h_area);
+				} else {
+					tetromino_cell(f, h_area, modifiers.shown_kind(kind));
+				}
+			};
+		}
+	}
+}
@@ -12,11 +12,16 @@ pub fn menu(f: &mut Frame, rect: Rect, menuable: &dyn Menu) {
 	let cursor = menuable.cursor();
 	let items = menuable.items();
 
-	let v_chunks = Layout::vertical(vec![Constraint::Length(4); items.len()])
+	// Scroll when there are more items than the area can hold
+	let capacity = ((rect.height + 2) / 6).max(1) as usize;
+	let start = (cursor + 1).saturating_sub(capacity);
+	let end = items.len().min(start + capacity);
+
+	let v_chunks = Layout::vertical(vec![Constraint::Length(4); end - start])
 		.spacing(2)
 		.split(rect);
 
-	for (i, item) in items.iter().enumerate() {
+	for (i, item) in items.iter().enumerate().take(end).skip(start) {
 		let title = BigText::builder()
 			.pixel_size(PixelSize::Quadrant)
 			.lines([Line::raw(item)])
@@ -33,7 +38,7 @@ pub fn menu(f: &mut Frame, rect: Rect, menuable: &dyn Menu) {
 		let title_area =
 			Layout::horizontal([Constraint::Length(item.len() as u16 * 4)])
 				.flex(Flex::Center)
-				.areas::<1>(v_chunks[i])[0];
+				.areas::<1>(v_chunks[i - start])[0];
 
 		f.render_widget(title, title_area);
 	}
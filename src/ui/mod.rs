@@ -2,13 +2,18 @@ mod about;
 mod board;
 mod cell;
 mod count_down;
+mod editor;
 mod game_over_menu;
 mod help;
+mod hold_board;
 mod loading;
 mod menu;
+mod mode_menu;
+mod modifier_menu;
 mod next_board;
 mod particles;
 mod pause_menu;
+mod puzzle_menu;
 mod scores;
 mod setting_menu;
 mod sidebar;
@@ -18,13 +23,17 @@ mod utils;
 use about::about;
 use board::main_board;
 use count_down::count_down;
+use editor::editor;
 use game_over_menu::game_over_menu;
 use help::help;
 pub use loading::loading;
+use mode_menu::mode_menu;
+use modifier_menu::modifier_menu;
 use particles::particles;
 use pause_menu::pause_menu;
+use puzzle_menu::puzzle_menu;
 use ratatui::{
-	layout::{Constraint, Flex, Layout},
+	layout::{Constraint, Flex, Layout, Rect},
 	style::{Color, Style},
 	widgets::{Block, BorderType, Borders},
 	Frame,
@@ -35,15 +44,13 @@ use sidebar::sidebar;
 use start_menu::start_menu;
 
 use crate::{
-	consts::{
-		MAIN_BOARD_COLS, MAIN_BOARD_VISIBLE_ROWS, MIN_CELL_HEIGHT,
-		MIN_CELL_WIDTH,
-	},
+	consts::{MAIN_BOARD_COLS, MIN_CELL_HEIGHT, MIN_CELL_WIDTH},
+	global::{board_cols, board_visible_rows},
 	state::{focus::Scene, State},
 };
 
-const ROWS: u16 = MAIN_BOARD_VISIBLE_ROWS as u16;
-const COLS: u16 = MAIN_BOARD_COLS as u16;
+/// The sidebar keeps the width of a default board
+const SIDEBAR_COLS: u16 = MAIN_BOARD_COLS as u16;
 
 pub fn ui(f: &mut Frame, state: &mut State) {
 	let screen = f.size();
@@ -60,30 +67,32 @@ pub fn ui(f: &mut Frame, state: &mut State) {
 	}
 
 	if state.focus.contains(Scene::Game) {
-		let (cell_height, cell_width) = calc_cell_size(screen.height);
+		// A big board is drawn with cells twice as large in each direction
+		let scale = state.modifiers.scale();
+		let rows = board_visible_rows() as u16 * scale;
+		let cols = board_cols() as u16 * scale;
+
+		let (cell_height, cell_width) = calc_cell_size(screen, rows, cols);
 
 		let vertical_area =
-			Layout::vertical([Constraint::Length(cell_height * ROWS)])
+			Layout::vertical([Constraint::Length(cell_height * rows)])
 				.flex(Flex::Center)
 				.areas::<1>(screen)[0];
 
-		let horizontal_area = Layout::horizontal([
-			Constraint::Percentage(50),
-			Constraint::Percentage(50),
+		let [left_area, right_area] = Layout::horizontal([
+			Constraint::Length(cell_width * cols),
+			Constraint::Length(cell_width * SIDEBAR_COLS),
 		])
+		.flex(Flex::Center)
 		.areas::<2>(vertical_area);
 
-		let left_area =
-			Layout::horizontal([Constraint::Length(cell_width * COLS)])
-				.flex(Flex::End)
-				.areas::<1>(horizontal_area[0])[0];
-
-		let right_area =
-			Layout::horizontal([Constraint::Length(cell_width * COLS)])
-				.flex(Flex::Start)
-				.areas::<1>(horizontal_area[1])[0];
-
-		main_board(f, left_area, state, cell_height, cell_width);
+		main_board(
+			f,
+			left_area,
+			state,
+			cell_height * scale,
+			cell_width * scale,
+		);
 
 		sidebar(f, right_area, state, cell_height, cell_width);
 
@@ -95,15 +104,31 @@ pub fn ui(f: &mut Frame, state: &mut State) {
 	}
 
 	if state.focus.contains(Scene::GameOverMenu) {
-		game_over_menu(f, &state.game_over_menu);
+		game_over_menu(f, &state.game_over_menu, state.bag.seed());
 	}
 
 	if state.focus.contains(Scene::PauseMenu) {
 		pause_menu(f, &state.pause_menu);
 	}
 
+	if state.focus.contains(Scene::ModeMenu) {
+		mode_menu(f, &state.mode_menu);
+	}
+
+	if state.focus.contains(Scene::PuzzleMenu) {
+		puzzle_menu(f, &state.puzzle_menu);
+	}
+
+	if state.focus.contains(Scene::ModifierMenu) {
+		modifier_menu(f, &state.modifier_menu);
+	}
+
+	if state.focus.contains(Scene::Editor) {
+		editor(f, screen, &state.editor);
+	}
+
 	if state.focus.contains(Scene::Scores) {
-		scores(f, &state.scores);
+		scores(f, state);
 	}
 
 	if state.focus.contains(Scene::SettingMenu) {
@@ -119,8 +144,11 @@ pub fn ui(f: &mut Frame, state: &mut State) {
 	}
 }
 
-fn calc_cell_size(screen_height: u16) -> (u16, u16) {
-	let height = MIN_CELL_HEIGHT.max(screen_height / ROWS);
+fn calc_cell_size(screen: Rect, rows: u16, cols: u16) -> (u16, u16) {
+	// Cells are about 1.8 times as wide as they are high
+	let fit_width =
+		(screen.width as f32 / ((cols + SIDEBAR_COLS) as f32 * 1.8)) as u16;
+	let height = MIN_CELL_HEIGHT.max((screen.height / rows).min(fit_width));
 	let width = MIN_CELL_WIDTH.max((height as f32 * 1.8) as u16);
 
 	(height, width)
@@ -0,0 +1,13 @@
+use ratatui::{widgets::Padding, Frame};
+
+use super::{menu::menu, utils::Popup};
+use crate::state::mode_menu::ModeMenu;
+
+pub fn mode_menu(f: &mut Frame, mode_menu: &ModeMenu) {
+	let popup = Popup::new(56, 34)
+		.title("MODE")
+		.padding(Padding::vertical(2))
+		.render(f);
+
+	menu(f, popup, mode_menu);
+}
@@ -0,0 +1,39 @@
+use ratatui::{
+	layout::{Constraint, Layout},
+	style::{Style, Stylize},
+	text::Line,
+	widgets::{Padding, Paragraph},
+	Frame,
+};
+
+use super::{menu::menu, utils::Popup};
+use crate::state::{modifier_menu::ModifierMenu, modifiers::Modifiers};
+
+pub fn modifier_menu(f: &mut Frame, modifier_menu: &ModifierMenu) {
+	let popup = Popup::new(88, 28)
+		.title("MODIFIERS")
+		.padding(Padding::new(0, 0, 2, 1))
+		.render(f);
+
+	let [list_area, tag_area] =
+		Layout::vertical([Constraint::Min(0), Constraint::Length(1)])
+			.areas::<2>(popup);
+
+	menu(f, list_area, modifier_menu);
+
+	let tag = Modifiers::from_setting().tag();
+
+	let p = if tag.is_empty() {
+		Paragraph::new(Line::styled(
+			"SCORES GO TO THE NORMAL LEADERBOARD",
+			Style::new().dark_gray(),
+		))
+	} else {
+		Paragraph::new(Line::styled(
+			format!("SCORES ARE TAGGED {}", tag),
+			Style::new().light_yellow(),
+		))
+	};
+
+	f.render_widget(p.centered(), tag_area);
+}
@@ -5,9 +5,9 @@ use ratatui::{
 
 use super::cell::tetromino_cell;
 use crate::{
-	common::Board,
+	common::TetrominoKind,
 	consts::{NEXT_BOARD_COLS, NEXT_BOARD_ROWS},
-	state::next_board::NextBoard,
+	state::{modifiers::Modifiers, next_board::NextBoard},
 };
 
 pub fn next_board(
@@ -16,21 +16,36 @@ pub fn next_board(
 	next_board: &NextBoard,
 	cell_height: u16,
 	cell_width: u16,
+	modifiers: &Modifiers,
 ) {
-	let v_chunks =
-		Layout::vertical([Constraint::Length(cell_height); NEXT_BOARD_ROWS])
-			.areas::<NEXT_BOARD_ROWS>(rect);
+	let slot_height = cell_height * NEXT_BOARD_ROWS as u16;
+	let fit = ((rect.height + 1) / (slot_height + 1)) as usize;
+	let kinds: Vec<&TetrominoKind> = next_board.visible().take(fit).collect();
 
-	for (y, v_area) in v_chunks.into_iter().enumerate() {
-		let h_chunks = Layout::horizontal(
-			[Constraint::Length(cell_width); NEXT_BOARD_COLS],
+	let slots =
+		Layout::vertical(vec![Constraint::Length(slot_height); kinds.len()])
+			.spacing(1)
+			.split(rect);
+
+	for (kind, slot) in kinds.into_iter().zip(slots.iter()) {
+		let position = kind.init_position(0);
+
+		let v_chunks = Layout::vertical(
+			[Constraint::Length(cell_height); NEXT_BOARD_ROWS],
 		)
-		.areas::<NEXT_BOARD_COLS>(v_area);
+		.areas::<NEXT_BOARD_ROWS>(*slot);
+
+		for (y, v_area) in v_chunks.into_iter().enumerate() {
+			let h_chunks = Layout::horizontal(
+				[Constraint::Length(cell_width); NEXT_BOARD_COLS],
+			)
+			.areas::<NEXT_BOARD_COLS>(v_area);
 
-		for (x, h_area) in h_chunks.into_iter().enumerate() {
-			if let Some(kind) = next_board.get_kind(x, y) {
-				tetromino_cell(f, h_area, kind);
-			};
+			for (x, h_area) in h_chunks.into_iter().enumerate() {
+				if position.contains(x, y) {
+					tetromino_cell(f, h_area, modifiers.shown_kind(kind));
+				}
+			}
 		}
 	}
 }
@@ -0,0 +1,93 @@
+use ratatui::{
+	layout::{Constraint, Layout},
+	style::{Style, Stylize},
+	text::{Line, Text},
+	widgets::{Padding, Paragraph, Wrap},
+	Frame,
+};
+
+use super::{menu::menu, utils::Popup};
+use crate::state::puzzle_menu::PuzzleMenu;
+
+pub fn puzzle_menu(f: &mut Frame, puzzle_menu: &PuzzleMenu) {
+	let popup = Popup::new(72, 42)
+		.title("PUZZLE")
+		.padding(Padding::new(2, 2, 2, 1))
+		.render(f);
+
+	let mut errors: Vec<Line> = puzzle_menu
+		.errors
+		.iter()
+		.take(ERRORS_SHOWN)
+		.map(|error| Line::styled(error.as_str(), Style::new().dark_gray()))
+		.collect();
+	if puzzle_menu.errors.len() > ERRORS_SHOWN {
+		errors.push(Line::styled(
+			format!(
+				"AND {} MORE FILES THAT FAILED TO PARSE",
+				puzzle_menu.errors.len() - ERRORS_SHOWN
+			),
+			Style::new().dark_gray(),
+		));
+	}
+
+	let [list_area, errors_area, info_area] = Layout::vertical([
+		Constraint::Min(0),
+		Constraint::Length(errors.len() as u16),
+		Constraint::Length(3),
+	])
+	.spacing(1)
+	.areas::<3>(popup);
+
+	f.render_widget(Paragraph::new(Text::from(errors)), errors_area);
+
+	let Some(puzzle) = puzzle_menu.current() else {
+		let dir = puzzle_menu
+			.dir
+			.as_ref()
+			.map(|dir| dir.display().to_string())
+			.unwrap_or_default();
+		let p = Paragraph::new(Text::from(vec![
+			Line::styled("NO PUZZLES FOUND", Style::new().white().bold()),
+			Line::raw(""),
+			Line::styled("PUT JSON OR TOML FILES IN", Style::new().dark_gray()),
+			Line::styled(dir, Style::new().dark_gray()),
+		]))
+		.centered()
+		.wrap(Wrap {
+			trim: true,
+		});
+		f.render_widget(p, list_area);
+		return;
+	};
+
+	menu(f, list_area, puzzle_menu);
+
+	let pieces = if puzzle.setup.pieces.is_empty() {
+		"RANDOM".to_string()
+	} else {
+		format!("{}", puzzle.setup.pieces.len())
+	};
+
+	let info = Paragraph::new(Text::from(vec![
+		Line::styled(
+			format!("GOAL: {}", puzzle.objective.text()),
+			Style::new().white().bold(),
+		),
+		Line::styled(format!("PIECES: {}", pieces), Style::new().dark_gray()),
+		Line::styled(
+			if puzzle_menu.solved.contains(&puzzle.id) {
+				"SOLVED"
+			} else {
+				""
+			},
+			Style::new().green(),
+		),
+	]))
+	.centered();
+
+	f.render_widget(info, info_area);
+}
+
+/// Files that failed to parse listed under the puzzles
+const ERRORS_SHOWN: usize = 3;
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use ratatui::{
 	style::{Style, Stylize},
 	text::Line,
@@ -6,19 +8,105 @@ use ratatui::{
 use tui_big_text::{BigText, PixelSize};
 
 use super::utils::Popup;
-use crate::state::scores::Scores;
+use crate::{
+	global::global_setting,
+	state::{
+		mode_menu::dig_rows,
+		modifiers::Modifiers,
+		scores::{leaderboard_tag, Leaderboards, ScoreRecord, ScoresPage},
+		stopwatch::format_time,
+		State,
+	},
+};
+
+/// Shows the leaderboards of the selected modifiers and scoring
+pub fn scores(f: &mut Frame, state: &State) {
+	let tag =
+		leaderboard_tag(&Modifiers::from_setting(), global_setting().scoring());
+	let empty = Leaderboards::default();
+	let leaderboards = state.leaderboards(&tag).unwrap_or(&empty);
+
+	let (name, records): (String, Vec<String>) = match state.scores_page {
+		ScoresPage::Marathon => {
+			(
+				"HIGH SCORES".to_string(),
+				leaderboards.scores.iter().map(score_text).collect(),
+			)
+		}
+		ScoresPage::Endless => {
+			(
+				"ENDLESS".to_string(),
+				leaderboards.endless_scores.iter().map(score_text).collect(),
+			)
+		}
+		ScoresPage::Sprint => {
+			let lines = global_setting().sprint_lines();
+			(
+				format!("SPRINT {}L", lines),
+				leaderboards
+					.sprint_times
+					.iter(lines)
+					.map(|time| format_time(Duration::from_millis(*time)))
+					.chain(std::iter::repeat("-".to_string()))
+					.take(10)
+					.collect(),
+			)
+		}
+		ScoresPage::Ultra => {
+			let minutes = global_setting().ultra_minutes();
+			(
+				format!("ULTRA {}MIN", minutes),
+				leaderboards
+					.ultra_scores
+					.iter(minutes)
+					.map(|record| record.score.to_string())
+					.chain(std::iter::repeat("0".to_string()))
+					.take(10)
+					.collect(),
+			)
+		}
+		ScoresPage::Dig => {
+			let rows = dig_rows() as u32;
+			(
+				format!("DIG {}L", rows),
+				leaderboards
+					.dig_records
+					.iter(rows)
+					.map(|record| {
+						format!(
+							"{} {:>3}P",
+							format_time(Duration::from_millis(record.time)),
+							record.pieces
+						)
+					})
+					.chain(std::iter::repeat("-".to_string()))
+					.take(10)
+					.collect(),
+			)
+		}
+	};
+
+	let title = if tag.is_empty() {
+		format!("< {} >", name)
+	} else {
+		format!("< {} | {} >", name, tag)
+	};
 
-pub fn scores(f: &mut Frame, scores: &Scores) {
-	let popup = Popup::new(58, 42).title("HIGH SCORES").render(f);
+	let record_width =
+		records.iter().map(|r| r.len()).max().unwrap_or(0).max(12);
 
-	let lines: Vec<Line> = scores
+	let width = (record_width as u16 + 2) * 4 + 2;
+
+	let popup = Popup::new(width, 42).title(title).render(f);
+
+	let lines: Vec<Line> = records
 		.iter()
 		.enumerate()
-		.map(|(i, score)| {
+		.map(|(i, record)| {
 			let s = if i >= 9 {
-				format!("{}.{:>11}", i + 1, score)
+				format!("{}.{:>w$}", i + 1, record, w = record_width - 1)
 			} else {
-				format!("{}.{:>12}", i + 1, score)
+				format!("{}.{:>w$}", i + 1, record, w = record_width)
 			};
 			Line::raw(s)
 		})
@@ -33,3 +121,12 @@ pub fn scores(f: &mut Frame, scores: &Scores) {
 
 	f.render_widget(text, popup);
 }
+
+/// The start level is left out of the empty slots
+fn score_text(record: &ScoreRecord) -> String {
+	if record.score == 0 {
+		"0".to_string()
+	} else {
+		format!("{} LV{}", record.score, record.start_level)
+	}
+}
@@ -1,14 +1,26 @@
 use ratatui::{
 	layout::{Constraint, Flex, Layout, Rect},
-	style::{Color, Style},
-	text::Line,
+	style::{Color, Style, Stylize},
+	text::{Line, Text},
+	widgets::Paragraph,
 	Frame,
 };
 use tui_big_text::{BigText, PixelSize};
-use Constraint::{Length, Ratio};
+use Constraint::{Length, Min, Ratio};
 
-use super::{next_board::next_board, utils::rounded_block};
-use crate::{consts::NEXT_BOARD_ROWS, state::State};
+use super::{
+	hold_board::hold_board, next_board::next_board, utils::rounded_block,
+};
+use crate::{
+	consts::{HOLD_BOARD_COLS, HOLD_BOARD_ROWS, NEXT_BOARD_COLS},
+	state::{
+		game_mode::{GameMode, MARATHON_LINES},
+		puzzle::Objective,
+		stats::Stats,
+		stopwatch::format_time,
+		State,
+	},
+};
 
 pub fn sidebar(
 	f: &mut Frame,
@@ -24,31 +36,146 @@ pub fn sidebar(
 
 	f.render_widget(sidebar_blcok, rect);
 
-	let vertical_chunks =
-		Layout::vertical([Ratio(1, 4); 4]).areas::<4>(sidebar_area);
+	let columns = Layout::horizontal([Ratio(1, 2); 2]).areas::<2>(sidebar_area);
+
+	let left_chunks = Layout::vertical([
+		Length(cell_height * (HOLD_BOARD_ROWS as u16) + 6),
+		Length(3),
+		Ratio(1, 3),
+		Ratio(1, 3),
+		Ratio(1, 3),
+	])
+	.areas::<5>(columns[0]);
+
+	let hold_area =
+		Layout::horizontal([Length(cell_width * HOLD_BOARD_COLS as u16)])
+			.flex(Flex::Center)
+			.areas::<1>(left_chunks[0])[0];
 
-	let chunks_0 = Layout::vertical([
+	let hold_chunks = Layout::vertical([
 		Length(4),
-		Length(cell_height * (NEXT_BOARD_ROWS as u16) + 1),
+		Length(cell_height * (HOLD_BOARD_ROWS as u16) + 1),
 	])
 	.spacing(1)
 	.flex(Flex::Center)
-	.areas::<2>(vertical_chunks[0]);
+	.areas::<2>(hold_area);
 
-	render_text(f, "NEXT".to_string(), chunks_0[0], Color::Blue);
-	next_board(f, chunks_0[1], &state.next_board, cell_height, cell_width);
+	render_text(f, "HOLD".to_string(), hold_chunks[0], Color::Blue);
+	hold_board(
+		f,
+		hold_chunks[1],
+		&state.hold_board,
+		cell_height,
+		cell_width,
+		&state.modifiers,
+	);
 
-	let chunks_1 = create_text_chunks(vertical_chunks[1]);
+	render_clear(f, left_chunks[1], &state.stats);
+
+	let chunks_1 = create_text_chunks(left_chunks[2]);
 	render_text(f, "SCORE".to_string(), chunks_1[0], Color::Blue);
 	render_text(f, state.stats.score.to_string(), chunks_1[1], Color::White);
 
-	let chunks_2 = create_text_chunks(vertical_chunks[2]);
-	render_text(f, "LEVEL".to_string(), chunks_2[0], Color::Blue);
-	render_text(f, state.stats.level.to_string(), chunks_2[1], Color::White);
+	let chunks_2 = create_text_chunks(left_chunks[3]);
+	if state.mode.is_timed() {
+		render_text(f, "TIME".to_string(), chunks_2[0], Color::Blue);
+		render_text(
+			f,
+			format_time(
+				state
+					.mode
+					.remaining_time(&state.stopwatch)
+					.unwrap_or(state.stopwatch.elapsed()),
+			),
+			chunks_2[1],
+			Color::White,
+		);
+	} else {
+		render_text(f, "LEVEL".to_string(), chunks_2[0], Color::Blue);
+		render_text(
+			f,
+			state.stats.level.to_string(),
+			chunks_2[1],
+			Color::White,
+		);
+	}
+
+	let (label, lines) = match state.mode {
+		GameMode::Marathon => {
+			("LINES", format!("{}/{}", state.stats.lines, MARATHON_LINES))
+		}
+		GameMode::Sprint {
+			lines,
+		} => ("LINES", format!("{}/{}", state.stats.lines, lines)),
+		GameMode::Dig {
+			..
+		} => ("GARBAGE", state.stats.garbage_left.to_string()),
+		GameMode::Puzzle {
+			objective: Objective::Survive(pieces),
+		} => ("PIECES", format!("{}/{}", state.stats.pieces, pieces)),
+		GameMode::Puzzle {
+			..
+		} => ("PIECES", state.stats.pieces.to_string()),
+		_ => ("LINES", state.stats.lines.to_string()),
+	};
+	let chunks_3 = create_text_chunks(left_chunks[4]);
+	render_text(f, label.to_string(), chunks_3[0], Color::Blue);
+	render_text(f, lines, chunks_3[1], Color::White);
+
+	if state.modifiers.no_next {
+		return;
+	}
+
+	let next_area =
+		Layout::horizontal([Length(cell_width * NEXT_BOARD_COLS as u16)])
+			.flex(Flex::Center)
+			.areas::<1>(columns[1])[0];
 
-	let chunks_3 = create_text_chunks(vertical_chunks[3]);
-	render_text(f, "LINES".to_string(), chunks_3[0], Color::Blue);
-	render_text(f, state.stats.lines.to_string(), chunks_3[1], Color::White);
+	let next_chunks = Layout::vertical([Length(4), Min(0)])
+		.spacing(1)
+		.vertical_margin(cell_height / 2)
+		.areas::<2>(next_area);
+
+	render_text(f, "NEXT".to_string(), next_chunks[0], Color::Blue);
+	next_board(
+		f,
+		next_chunks[1],
+		&state.next_board,
+		cell_height,
+		cell_width,
+		&state.modifiers,
+	);
+}
+
+fn render_clear(f: &mut Frame, rect: Rect, stats: &Stats) {
+	let (action, perfect_clear) = match stats.last_clear {
+		Some(action) => (action.text(), action.perfect_clear),
+		None => (String::new(), false),
+	};
+
+	let counter = if let Some(grade) = stats.grade() {
+		format!("GRADE {}  T-SPINS {}", grade, stats.t_spins)
+	} else if stats.b2b > 0 {
+		format!("T-SPINS {}  B2B x{}", stats.t_spins, stats.b2b)
+	} else {
+		format!("T-SPINS {}", stats.t_spins)
+	};
+
+	let p = Paragraph::new(Text::from(vec![
+		Line::styled(action, Style::new().magenta().bold()),
+		Line::styled(
+			if perfect_clear {
+				"PERFECT CLEAR"
+			} else {
+				""
+			},
+			Style::new().light_yellow().bold(),
+		),
+		Line::styled(counter, Style::new().dark_gray()),
+	]))
+	.centered();
+
+	f.render_widget(p, rect);
 }
 
 fn create_text_chunks(rect: Rect) -> [Rect; 2] {
@@ -59,7 +186,19 @@ fn create_text_chunks(rect: Rect) -> [Rect; 2] {
 }
 
 fn render_text(f: &mut Frame, text: String, rect: Rect, color: Color) {
-	let area = Layout::horizontal([Length(text.len() as u16 * 4)])
+	let width = text.len() as u16 * 4;
+
+	// Fall back to normal text when the big text would be clipped
+	if width > rect.width {
+		let area = Layout::vertical([Length(1)])
+			.flex(Flex::Center)
+			.areas::<1>(rect)[0];
+		let p = Paragraph::new(text).centered().fg(color).bold();
+		f.render_widget(p, area);
+		return;
+	}
+
+	let area = Layout::horizontal([Length(width)])
 		.flex(Flex::Center)
 		.areas::<1>(rect)[0];
 
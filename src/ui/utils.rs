@@ -28,7 +28,7 @@ pub fn rounded_block() -> Block<'static> {
 pub struct Popup {
 	width: u16,
 	height: u16,
-	title: Option<&'static str>,
+	title: Option<String>,
 	padding: Option<Padding>,
 }
 
@@ -42,8 +42,8 @@ impl Popup {
 		}
 	}
 
-	pub fn title(mut self, title: &'static str) -> Self {
-		self.title = Some(title);
+	pub fn title(mut self, title: impl Into<String>) -> Self {
+		self.title = Some(title.into());
 		self
 	}
 